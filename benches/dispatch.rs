@@ -0,0 +1,106 @@
+//! 曲线与进度计算的分发方式对比: 函数指针 与 match
+//!
+//! cargo +nightly bench --bench dispatch
+#![feature(test)]
+#![allow(deprecated)]
+extern crate test;
+
+use pi_curves::{amount::AnimationAmountCalc, curve::{frame_curve::FrameCurve, FrameIndex}, easing::EEasingMode, steps::EStepMode, AmountParam, EAmountMode};
+use test::{black_box, Bencher};
+
+const CURVE_COUNT: usize = 10_000;
+
+/// 各类型曲线交替排列, 避免分支预测只命中一种类型
+fn mixed_curves() -> Vec<FrameCurve<f32, f32>> {
+    let frame_count: FrameIndex = 60;
+    (0..CURVE_COUNT).map(|index| match index % 6 {
+        0 => {
+            let mut curve = FrameCurve::curve_frame_values(60);
+            curve.curve_frame_values_frame(0, 0.0f32);
+            curve.curve_frame_values_frame(frame_count, 1.0f32);
+            curve
+        },
+        1 => {
+            let mut curve = FrameCurve::curve_frame_values_step(60);
+            curve.curve_frame_values_frame(0, 0.0f32);
+            curve.curve_frame_values_frame(frame_count, 1.0f32);
+            curve
+        },
+        2 => FrameCurve::curve_easing(0.0f32, 1.0f32, frame_count, 60, EEasingMode::CubicInOut),
+        3 => {
+            let mut curve = FrameCurve::curve_minmax_curve(0.0f32, 1.0f32, 60);
+            curve.curve_minmax_curve_frame(0, 0.0f32, 2.0f32, 2.0f32);
+            curve.curve_minmax_curve_frame(frame_count, 1.0f32, 2.0f32, 2.0f32);
+            curve
+        },
+        4 => FrameCurve::curve_cubic_bezier(0.0f32, 1.0f32, frame_count, 60, 0.25, 0.1, 0.25, 1.0),
+        _ => {
+            let mut curve = FrameCurve::curve_cubic_spline(60);
+            curve.curve_cubic_splice_frame(0, 0.0f32, 0.0f32, 0.0f32);
+            curve.curve_cubic_splice_frame(frame_count, 1.0f32, 0.0f32, 0.0f32);
+            curve
+        },
+    }).collect()
+}
+
+#[bench]
+fn bench_curve_fn_pointer(b: &mut Bencher) {
+    let curves = mixed_curves();
+    let calls: Vec<_> = curves.iter().map(|curve| black_box(curve.call)).collect();
+    let amountcalc = AnimationAmountCalc::default();
+    b.iter(|| {
+        let mut v = 0.;
+        for (curve, call) in curves.iter().zip(calls.iter()) {
+            v += call(curve, black_box(0.5) * 60., &amountcalc);
+        }
+        v
+    });
+}
+
+#[bench]
+fn bench_curve_match(b: &mut Bencher) {
+    let curves = mixed_curves();
+    let amountcalc = AnimationAmountCalc::default();
+    b.iter(|| {
+        let mut v = 0.;
+        for curve in curves.iter() {
+            v += curve.interple(black_box(0.5), &amountcalc);
+        }
+        v
+    });
+}
+
+fn mixed_modes() -> Vec<EAmountMode> {
+    (0..CURVE_COUNT).map(|index| match index % 4 {
+        0 => EAmountMode::None,
+        1 => EAmountMode::Easing(EEasingMode::QuadInOut),
+        2 => EAmountMode::Steps(EStepMode::JumpEnd),
+        _ => EAmountMode::CubicBezier,
+    }).collect()
+}
+
+#[bench]
+fn bench_amount_fn_pointer(b: &mut Bencher) {
+    let calls: Vec<fn(f32, &AmountParam<f32>) -> f32> = mixed_modes().into_iter().map(|mode| black_box(EAmountMode::get_transform_amount_call(mode))).collect();
+    let param = AmountParam(4., 0.25, 0.1, 0.25);
+    b.iter(|| {
+        let mut v = 0.;
+        for call in calls.iter() {
+            v += call(black_box(0.4), &param);
+        }
+        v
+    });
+}
+
+#[bench]
+fn bench_amount_match(b: &mut Bencher) {
+    let modes = mixed_modes();
+    let param = AmountParam(4., 0.25, 0.1, 0.25);
+    b.iter(|| {
+        let mut v = 0.;
+        for mode in modes.iter() {
+            v += mode.transform_amount(black_box(0.4), &param);
+        }
+        v
+    });
+}
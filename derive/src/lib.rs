@@ -0,0 +1,213 @@
+//! 为自定义结构体派生 `pi_curves::curve::frame::FrameDataValue`
+//!
+//! 各方法逐字段委托给字段类型自身的实现, 字段可通过属性调整插值行为:
+//! * `#[frame_data(slerp)]` - `interpolate` 使用 `FrameValueSlerp::slerp` (旋转类数据)
+//! * `#[frame_data(step)]` - 进度不足 0.5 取起始值, 否则取目标值, 不做插值
+//!
+//! 默认只实现 `FrameDataValue<f32>`, 结构体上可用 `#[frame_data(scalar(f32, f64))]` 指定计算标量类型
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Index, Member, Type};
+
+/// 字段插值方式
+#[derive(Clone, Copy, PartialEq)]
+enum EFieldMode {
+    Interpolate,
+    Slerp,
+    Step,
+}
+
+struct FieldInfo {
+    member: Member,
+    ty: Type,
+    mode: EFieldMode,
+}
+
+#[proc_macro_derive(FrameDataValue, attributes(frame_data))]
+pub fn derive_frame_data_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => collect_fields(&data.fields)?,
+        _ => return Err(syn::Error::new_spanned(&input.ident, "FrameDataValue can only be derived for structs")),
+    };
+
+    let mut scalars = struct_scalars(&input)?;
+    if scalars.is_empty() {
+        scalars.push(parse_quote!(f32));
+    }
+
+    let impls = scalars.iter().map(|scalar| expand_scalar(&input, &fields, scalar));
+    let size = expand_size(&input, &fields);
+    Ok(quote!(#(#impls)* #size))
+}
+
+fn expand_scalar(input: &DeriveInput, fields: &[FieldInfo], scalar: &Type) -> TokenStream2 {
+    let frame = quote!(::pi_curves::curve::frame);
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = &field.ty;
+        where_clause.predicates.push(parse_quote!(#ty: #frame::FrameDataValue<#scalar>));
+        if field.mode == EFieldMode::Slerp {
+            where_clause.predicates.push(parse_quote!(#ty: #frame::FrameValueSlerp<#scalar>));
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let interpolate = fields.iter().map(|field| {
+        let FieldInfo { member, ty, mode } = field;
+        match mode {
+            EFieldMode::Interpolate => quote!(#member: <#ty as #frame::FrameDataValue<#scalar>>::interpolate(&self.#member, &rhs.#member, amount)),
+            EFieldMode::Slerp => quote!(#member: <#ty as #frame::FrameValueSlerp<#scalar>>::slerp(&self.#member, &rhs.#member, amount)),
+            EFieldMode::Step => quote!(#member: <#ty as #frame::FrameDataValue<#scalar>>::interpolate(&self.#member, &rhs.#member, step)),
+        }
+    });
+    let append = fields.iter().map(|field| {
+        let FieldInfo { member, ty, mode } = field;
+        let amount = if *mode == EFieldMode::Step { quote!(step) } else { quote!(amount) };
+        quote!(#member: <#ty as #frame::FrameDataValue<#scalar>>::append(&self.#member, &rhs.#member, #amount))
+    });
+    let delta = fields.iter().map(|field| {
+        let FieldInfo { member, ty, .. } = field;
+        quote!(#member: <#ty as #frame::FrameDataValue<#scalar>>::delta(&self.#member, &reference.#member))
+    });
+    let add_delta = fields.iter().map(|field| {
+        let FieldInfo { member, ty, mode } = field;
+        let amount = if *mode == EFieldMode::Step { quote!(step) } else { quote!(amount) };
+        quote!(#member: <#ty as #frame::FrameDataValue<#scalar>>::add_delta(&self.#member, &delta.#member, #amount))
+    });
+    let hermite = fields.iter().map(|field| {
+        let FieldInfo { member, ty, mode } = field;
+        let amount = if *mode == EFieldMode::Step { quote!(step) } else { quote!(amount) };
+        quote!(#member: <#ty as #frame::FrameDataValue<#scalar>>::hermite(&value1.#member, &tangent1.#member, &value2.#member, &tangent2.#member, #amount, frame_delta))
+    });
+    quote! {
+        impl #impl_generics #frame::FrameDataValue<#scalar> for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn interpolate(&self, rhs: &Self, amount: #scalar) -> Self {
+                let step: #scalar = if amount < 0.5 { 0. } else { 1. };
+                Self { #(#interpolate,)* }
+            }
+            #[allow(unused_variables)]
+            fn append(&self, rhs: &Self, amount: #scalar) -> Self {
+                let step: #scalar = if amount < 0.5 { 0. } else { 1. };
+                Self { #(#append,)* }
+            }
+            #[allow(unused_variables)]
+            fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: #scalar, frame_delta: #scalar) -> Self {
+                let step: #scalar = if amount < 0.5 { 0. } else { 1. };
+                Self { #(#hermite,)* }
+            }
+            #[allow(unused_variables)]
+            fn delta(&self, reference: &Self) -> Self {
+                Self { #(#delta,)* }
+            }
+            #[allow(unused_variables)]
+            fn add_delta(&self, delta: &Self, amount: #scalar) -> Self {
+                let step: #scalar = if amount < 0.5 { 0. } else { 1. };
+                Self { #(#add_delta,)* }
+            }
+        }
+    }
+}
+
+/// `FrameValueSize` 与标量类型无关, 只实现一次
+fn expand_size(input: &DeriveInput, fields: &[FieldInfo]) -> TokenStream2 {
+    let frame = quote!(::pi_curves::curve::frame);
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = &field.ty;
+        where_clause.predicates.push(parse_quote!(#ty: #frame::FrameValueSize));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let size = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote!(<#ty as #frame::FrameValueSize>::size())
+    });
+
+    quote! {
+        impl #impl_generics #frame::FrameValueSize for #name #ty_generics #where_clause {
+            fn size() -> usize {
+                0 #(+ #size)*
+            }
+        }
+    }
+}
+
+/// 结构体上 `#[frame_data(scalar(f32, f64))]` 指定的标量类型
+fn struct_scalars(input: &DeriveInput) -> syn::Result<Vec<Type>> {
+    let mut scalars = vec![];
+    for attr in &input.attrs {
+        if !attr.path().is_ident("frame_data") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("scalar") {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("f32") || meta.path.is_ident("f64") {
+                        let path = meta.path;
+                        scalars.push(parse_quote!(#path));
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported scalar, expected `f32` or `f64`"))
+                    }
+                })
+            } else {
+                Err(meta.error("unsupported frame_data attribute, expected `scalar(...)`"))
+            }
+        })?;
+    }
+    Ok(scalars)
+}
+
+fn collect_fields(fields: &Fields) -> syn::Result<Vec<FieldInfo>> {
+    let mut result = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        };
+        result.push(FieldInfo { member, ty: field.ty.clone(), mode: field_mode(field)? });
+    }
+    Ok(result)
+}
+
+fn field_mode(field: &syn::Field) -> syn::Result<EFieldMode> {
+    let mut mode = EFieldMode::Interpolate;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("frame_data") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let next = if meta.path.is_ident("slerp") {
+                EFieldMode::Slerp
+            } else if meta.path.is_ident("step") {
+                EFieldMode::Step
+            } else {
+                return Err(meta.error("unsupported frame_data attribute, expected `slerp` or `step`"));
+            };
+            if mode != EFieldMode::Interpolate && mode != next {
+                return Err(meta.error("`slerp` and `step` cannot be used together"));
+            }
+            mode = next;
+            Ok(())
+        })?;
+    }
+    Ok(mode)
+}
@@ -0,0 +1,75 @@
+use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+use pi_curves::{amount::AnimationAmountCalc, curve::{frame::{FrameDataValue, FrameValueSize, KeyFrameCurveValue}, frame_curve::FrameCurve}, easing::EEasingMode};
+
+#[derive(Debug, Clone, PartialEq, pi_curves::FrameDataValue)]
+struct Transform {
+    pos: Vector3<f32>,
+    scale: f32,
+    alpha: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, pi_curves::FrameDataValue)]
+struct Sprite {
+    #[frame_data(step)]
+    frame: u16,
+    #[frame_data(slerp)]
+    rotation: Quaternion<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq, pi_curves::FrameDataValue)]
+#[frame_data(scalar(f32, f64))]
+struct Pair<T: Clone>(T, T);
+
+#[test]
+fn test_derive_interpolate() {
+    let a = Transform { pos: Vector3::new(0., 0., 0.), scale: 1., alpha: 0 };
+    let b = Transform { pos: Vector3::new(2., 4., 6.), scale: 3., alpha: 255 };
+
+    let v = a.interpolate(&b, 0.5);
+    assert_eq!(v, Transform { pos: Vector3::new(1., 2., 3.), scale: 2., alpha: 128 });
+    assert_eq!(Transform::size(), Vector3::<f32>::size() + f32::size() + u8::size());
+
+    let v = Pair(0.0f32, 10.0f32).interpolate(&Pair(1.0, 20.0), 0.25);
+    assert_eq!(v, Pair(0.25, 12.5));
+
+    let v = Pair(0.0f64, 10.0f64).interpolate(&Pair(1.0, 20.0), 0.25f64);
+    assert_eq!(v, Pair(0.25, 12.5));
+}
+
+#[test]
+fn test_derive_curve() {
+    let from = Transform { pos: Vector3::new(0., 0., 0.), scale: 1., alpha: 0 };
+    let scalar = Transform { pos: Vector3::new(10., 0., 0.), scale: 1., alpha: 200 };
+    let curve = FrameCurve::curve_easing(from, scalar, 60, 60, EEasingMode::None);
+
+    let v = curve.interple(0.5, &AnimationAmountCalc::default());
+    assert_eq!(v, Transform { pos: Vector3::new(5., 0., 0.), scale: 1.5, alpha: 100 });
+
+    let mut curve = FrameCurve::curve_cubic_spline(60);
+    let zero = Pair(0.0f32, 0.0f32);
+    curve.curve_cubic_splice_frame(0, Pair(0.0f32, 1.0f32), zero.clone(), zero.clone());
+    curve.curve_cubic_splice_frame(60, Pair(1.0f32, 3.0f32), zero.clone(), zero.clone());
+    let v = curve.interple(0.5, &AnimationAmountCalc::default());
+    assert_eq!(v, Pair(0.5, 2.0));
+}
+
+#[test]
+fn test_derive_field_mode() {
+    let half_turn = UnitQuaternion::from_euler_angles(0., 0., std::f32::consts::FRAC_PI_2).into_inner();
+    let a = Sprite { frame: 0, rotation: Quaternion::identity() };
+    let b = Sprite { frame: 8, rotation: half_turn };
+
+    let v = a.interpolate(&b, 0.25);
+    assert_eq!(v.frame, 0);
+    let angle = UnitQuaternion::new_normalize(v.rotation).angle();
+    assert!((angle - std::f32::consts::FRAC_PI_8).abs() < 1e-5);
+    // 球面插值保持单位长度
+    assert!((v.rotation.norm() - 1.0).abs() < 1e-5);
+
+    let v = a.interpolate(&b, 0.75);
+    assert_eq!(v.frame, 8);
+
+    let hermite = |amount: KeyFrameCurveValue| Sprite::hermite(&a, &a, &b, &a, amount, 1.0).frame;
+    assert_eq!(hermite(0.4), 0);
+    assert_eq!(hermite(0.6), 8);
+}
@@ -1,27 +1,33 @@
+use alloc::sync::Arc;
 
-use crate::{EAmountMode, AmountParam, steps::EStepMode, curve::{frame::KeyFrameCurveValue, FrameIndex}, easing::EEasingMode};
+use crate::{EAmountMode, AmountParam, steps::EStepMode, curve::{frame::{KeyFrameCurveScalar, KeyFrameCurveValue}, FrameIndex}, easing::{EEasingMode, lut::EasingLut}};
 
-#[derive(Clone, Copy)]
-pub struct AnimationAmountCalc {
+/// 进度计算 - Lut 模式持有共享的查找表, 因此只实现 Clone
+#[derive(Clone)]
+pub struct AnimationAmountCalc<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
     mode: EAmountMode,
-    param: AmountParam,
-    call: fn(KeyFrameCurveValue, &AmountParam) -> KeyFrameCurveValue,
+    param: AmountParam<F>,
+    /// Lut 模式的查找表
+    lut: Option<Arc<EasingLut<F>>>,
 }
 
-impl Default for AnimationAmountCalc {
+impl<F: KeyFrameCurveScalar> Default for AnimationAmountCalc<F> {
     fn default() -> Self {
         Self {
             mode: EAmountMode::None,
             param: AmountParam::default(),
-            call: EAmountMode::get_transform_amount_call(EAmountMode::None),
+            lut: None,
         }
     }
 }
 
-impl AnimationAmountCalc {
+impl<F: KeyFrameCurveScalar> AnimationAmountCalc<F> {
     pub fn mode(&self) -> EAmountMode {
         self.mode
     }
+    pub fn param(&self) -> AmountParam<F> {
+        self.param
+    }
     pub fn from_steps(step: FrameIndex, mode: EStepMode) -> Self {
         if step < 1 {
             AnimationAmountCalc::default()
@@ -29,8 +35,8 @@ impl AnimationAmountCalc {
             let mode = EAmountMode::Steps(mode);
             Self {
                 mode,
-                param: AmountParam(step as KeyFrameCurveValue, 0., 0., 0.),
-                call: EAmountMode::get_transform_amount_call(mode),
+                param: AmountParam(F::from_frame_index(step), F::zero(), F::zero(), F::zero()),
+                lut: None,
             }
         }
     }
@@ -39,19 +45,36 @@ impl AnimationAmountCalc {
         Self {
             mode,
             param: AmountParam::default(),
-            call: EAmountMode::get_transform_amount_call(mode),
+            lut: None,
         }
     }
-    pub fn from_cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+    pub fn from_cubic_bezier(x1: F, y1: F, x2: F, y2: F) -> Self {
         let mode = EAmountMode::CubicBezier;
         Self {
             mode,
-            param: AmountParam(x1 as KeyFrameCurveValue, y1 as KeyFrameCurveValue, x2 as KeyFrameCurveValue, y2 as KeyFrameCurveValue),
-            call: EAmountMode::get_transform_amount_call(mode),
+            param: AmountParam(x1, y1, x2, y2),
+            lut: None,
         }
     }
-    pub fn calc(&self, amount: KeyFrameCurveValue) -> KeyFrameCurveValue {
-        let call = &self.call;
-        call(amount, &self.param)
+    /// 查表计算, 参数取查找表源进度计算的参数
+    /// 查找表以 Arc 共享, 最后一个持有者释放时回收
+    pub fn from_lut(lut: Arc<EasingLut<F>>) -> Self {
+        let mode = EAmountMode::Lut(lut.interpolation());
+        Self {
+            mode,
+            param: lut.source().param(),
+            lut: Some(lut),
+        }
+    }
+    /// Lut 模式的查找表
+    pub fn lut(&self) -> Option<&Arc<EasingLut<F>>> {
+        self.lut.as_ref()
+    }
+    #[inline]
+    pub fn calc(&self, amount: F) -> F {
+        match &self.lut {
+            Some(lut) => lut.calc(amount),
+            None => self.mode.transform_amount(amount, &self.param),
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+
+use crate::{amount::AnimationAmountCalc, curve::{frame::{FrameDataValue, FrameValueSlerp, KeyFrameCurveScalar, KeyFrameCurveValue}, frame_curve::FrameCurve}};
+
+/// 混合方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EBlendMode {
+    /// 按权重占比加权平均, 权重和不必为 1
+    #[default]
+    Normalized,
+    /// 按权重直接累加 (Σ value * weight), 不适用于旋转类数据
+    Additive,
+}
+
+/// 混合多个值
+///
+/// * [values] - (值, 权重), 权重 <= 0 的项被忽略
+/// * [mode] - 混合方式
+/// * [mix] - Normalized 模式下两值之间的插值方法, 如 `FrameDataValue::interpolate` 或 `FrameValueSlerp::slerp`
+///
+/// 没有有效权重时返回 None
+pub fn blend<T: FrameDataValue<F>, F: KeyFrameCurveScalar, I: IntoIterator<Item = (T, F)>>(values: I, mode: EBlendMode, mix: fn(&T, &T, F) -> T) -> Option<T> {
+    let mut values = values.into_iter().filter(|(_, weight)| *weight > F::zero());
+    let (mut result, mut total) = values.next()?;
+    match mode {
+        EBlendMode::Normalized => {
+            // 逐个向累计结果插值, 插值量为新项在已累计权重中的占比
+            for (value, weight) in values {
+                total += weight;
+                result = mix(&result, &value, weight / total);
+            }
+        },
+        EBlendMode::Additive => {
+            result = result.append(&result, total - F::one());
+            for (value, weight) in values {
+                result = result.append(&value, weight);
+            }
+        },
+    }
+    Some(result)
+}
+
+/// 混合通道 - 曲线及其权重
+pub struct BlendChannel<T: FrameDataValue<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub curve: FrameCurve<T, F>,
+    pub weight: F,
+}
+
+/// 进行中的淡入淡出
+#[derive(Clone)]
+struct CrossFade<F: KeyFrameCurveScalar> {
+    target: usize,
+    /// 开始时各通道的权重
+    from_weights: Vec<F>,
+    elapsed: F,
+    duration: F,
+    amountcalc: AnimationAmountCalc<F>,
+}
+
+/// 曲线混合器 - 以权重混合多条同类型曲线, 支持定时淡入淡出
+pub struct AnimationBlender<T: FrameDataValue<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    mode: EBlendMode,
+    mix: fn(&T, &T, F) -> T,
+    channels: Vec<BlendChannel<T, F>>,
+    fade: Option<CrossFade<F>>,
+}
+
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> AnimationBlender<T, F> {
+    /// 使用 `FrameDataValue::interpolate` 混合
+    pub fn new(mode: EBlendMode) -> Self {
+        Self::with_mix(mode, T::interpolate)
+    }
+    /// 自定义两值之间的插值方法
+    pub fn with_mix(mode: EBlendMode, mix: fn(&T, &T, F) -> T) -> Self {
+        Self {
+            mode,
+            mix,
+            channels: Vec::new(),
+            fade: None,
+        }
+    }
+    pub fn mode(&self) -> EBlendMode {
+        self.mode
+    }
+    pub fn channels(&self) -> &[BlendChannel<T, F>] {
+        &self.channels
+    }
+    /// 添加曲线, 返回通道序号
+    pub fn add(&mut self, curve: FrameCurve<T, F>, weight: F) -> usize {
+        self.channels.push(BlendChannel { curve, weight });
+        if let Some(fade) = &mut self.fade {
+            fade.from_weights.push(weight);
+        }
+        self.channels.len() - 1
+    }
+    pub fn weight(&self, index: usize) -> Option<F> {
+        self.channels.get(index).map(|channel| channel.weight)
+    }
+    /// 设置权重, 会终止进行中的淡入淡出
+    pub fn set_weight(&mut self, index: usize, weight: F) {
+        if let Some(channel) = self.channels.get_mut(index) {
+            self.fade = None;
+            channel.weight = weight;
+        }
+    }
+
+    /// 淡入淡出到指定通道 - 目标通道权重过渡到 1, 其余过渡到 0
+    ///
+    /// * [target] - 目标通道序号
+    /// * [duration] - 过渡时长 (秒), <= 0 时立即完成
+    /// * [amountcalc] - 过渡进度的缓动
+    ///
+    pub fn cross_fade(&mut self, target: usize, duration: F, amountcalc: AnimationAmountCalc<F>) {
+        if target >= self.channels.len() {
+            return;
+        }
+        self.fade = Some(CrossFade {
+            target,
+            from_weights: self.channels.iter().map(|channel| channel.weight).collect(),
+            elapsed: F::zero(),
+            duration,
+            amountcalc,
+        });
+        self.tick(F::zero());
+    }
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+    /// 推进淡入淡出
+    pub fn tick(&mut self, delta: F) {
+        let fade = match &mut self.fade {
+            Some(fade) => fade,
+            None => return,
+        };
+        fade.elapsed += delta;
+        let progress = if fade.duration <= F::zero() { F::one() } else { (fade.elapsed / fade.duration).clamp(F::zero(), F::one()) };
+        let amount = if progress >= F::one() { F::one() } else { fade.amountcalc.calc(progress) };
+
+        for (index, (channel, from)) in self.channels.iter_mut().zip(fade.from_weights.iter()).enumerate() {
+            let to = if index == fade.target { F::one() } else { F::zero() };
+            channel.weight = *from + (to - *from) * amount;
+        }
+        if progress >= F::one() {
+            self.fade = None;
+        }
+    }
+
+    /// 在指定时间 (秒) 采样所有通道并混合, 没有有效权重时返回 None
+    pub fn sample(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> Option<T> {
+        let values = self.channels.iter().filter(|channel| channel.weight > F::zero()).map(|channel| (channel.curve.interple(time, amountcalc), channel.weight));
+        blend(values, self.mode, self.mix)
+    }
+}
+
+impl<T: FrameDataValue<F> + FrameValueSlerp<F>, F: KeyFrameCurveScalar> AnimationBlender<T, F> {
+    /// 使用 `FrameValueSlerp::slerp` 混合, 用于旋转类数据
+    pub fn new_slerp() -> Self {
+        Self::with_mix(EBlendMode::Normalized, T::slerp)
+    }
+}
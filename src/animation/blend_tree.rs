@@ -0,0 +1,190 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{amount::AnimationAmountCalc, curve::frame::{KeyFrameCurveScalar, KeyFrameCurveValue}};
+
+use super::{pose::AnimationPose, state_machine::{AnimationParameters, EAnimationMotion}};
+
+/// 一维混合树 - 按参数在相邻阈值的两个动作之间线性分配权重
+pub struct BlendTree1D<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    /// 浮点参数名, 参数不存在时取 0
+    pub parameter: String,
+    /// (阈值, 动作), 按阈值升序
+    children: Vec<(F, EAnimationMotion<F>)>,
+}
+
+impl<F: KeyFrameCurveScalar> BlendTree1D<F> {
+    pub fn new(parameter: &str) -> Self {
+        Self {
+            parameter: String::from(parameter),
+            children: Vec::new(),
+        }
+    }
+    /// 添加动作, 按阈值排序插入
+    pub fn add(&mut self, threshold: F, motion: EAnimationMotion<F>) {
+        let index = self.children.partition_point(|(value, _)| *value <= threshold);
+        self.children.insert(index, (threshold, motion));
+    }
+    pub fn thresholds(&self) -> impl Iterator<Item = F> + '_ {
+        self.children.iter().map(|(threshold, _)| *threshold)
+    }
+    /// 各动作的权重 (按阈值升序), 超出阈值范围时取两端
+    pub fn weights(&self, value: F) -> Vec<F> {
+        let mut weights = vec![F::zero(); self.children.len()];
+        if weights.is_empty() {
+            return weights;
+        }
+        let next = self.children.partition_point(|(threshold, _)| *threshold <= value);
+        if next == 0 {
+            weights[0] = F::one();
+        } else if next == self.children.len() {
+            weights[next - 1] = F::one();
+        } else {
+            let (from, to) = (self.children[next - 1].0, self.children[next].0);
+            let amount = (value - from) / (to - from);
+            weights[next - 1] = F::one() - amount;
+            weights[next] = amount;
+        }
+        weights
+    }
+    pub(crate) fn motions_weights(&self, parameters: &AnimationParameters<F>) -> (Vec<&EAnimationMotion<F>>, Vec<F>) {
+        let value = parameters.float(&self.parameter).unwrap_or(F::zero());
+        (self.children.iter().map(|(_, motion)| motion).collect(), self.weights(value))
+    }
+}
+
+/// 二维混合方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EBlendTree2DMode {
+    /// 自由方向 - 在极坐标下计算, 适用于 方向 + 速度 (如八方向移动, 原点为待机)
+    #[default]
+    FreeformDirectional,
+    /// 自由笛卡尔 - 在直角坐标下计算, 适用于两个独立参数
+    FreeformCartesian,
+}
+
+/// 二维混合树 - 梯度带插值 (Gradient Band Interpolation) 分配权重
+pub struct BlendTree2D<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub parameter_x: String,
+    pub parameter_y: String,
+    pub mode: EBlendTree2DMode,
+    /// (位置, 动作)
+    children: Vec<([F; 2], EAnimationMotion<F>)>,
+}
+
+impl<F: KeyFrameCurveScalar> BlendTree2D<F> {
+    pub fn new(parameter_x: &str, parameter_y: &str, mode: EBlendTree2DMode) -> Self {
+        Self {
+            parameter_x: String::from(parameter_x),
+            parameter_y: String::from(parameter_y),
+            mode,
+            children: Vec::new(),
+        }
+    }
+    pub fn add(&mut self, position: [F; 2], motion: EAnimationMotion<F>) {
+        self.children.push((position, motion));
+    }
+    pub fn positions(&self) -> impl Iterator<Item = [F; 2]> + '_ {
+        self.children.iter().map(|(position, _)| *position)
+    }
+    /// 各动作的权重 (按添加顺序), 权重和为 1
+    pub fn weights(&self, point: [F; 2]) -> Vec<F> {
+        let positions: Vec<[F; 2]> = self.positions().collect();
+        let mut weights: Vec<F> = (0..positions.len()).map(|i| {
+            let mut weight = F::one();
+            for j in 0..positions.len() {
+                if i == j {
+                    continue;
+                }
+                let influence = match self.mode {
+                    EBlendTree2DMode::FreeformCartesian => cartesian_influence(positions[i], positions[j], point),
+                    EBlendTree2DMode::FreeformDirectional => directional_influence(positions[i], positions[j], point),
+                };
+                weight = weight.min(influence);
+            }
+            weight.max(F::zero())
+        }).collect();
+
+        let total = weights.iter().fold(F::zero(), |total, weight| total + *weight);
+        if total > F::zero() {
+            weights.iter_mut().for_each(|weight| *weight /= total);
+        }
+        weights
+    }
+    pub(crate) fn motions_weights(&self, parameters: &AnimationParameters<F>) -> (Vec<&EAnimationMotion<F>>, Vec<F>) {
+        let point = [
+            parameters.float(&self.parameter_x).unwrap_or(F::zero()),
+            parameters.float(&self.parameter_y).unwrap_or(F::zero()),
+        ];
+        (self.children.iter().map(|(_, motion)| motion).collect(), self.weights(point))
+    }
+}
+
+/// 混合树时长 - 各动作时长按权重加权
+pub(crate) fn blend_duration<F: KeyFrameCurveScalar>(motions: &[&EAnimationMotion<F>], weights: &[F], parameters: &AnimationParameters<F>) -> F {
+    motions.iter().zip(weights.iter()).fold(F::zero(), |duration, (motion, weight)| duration + motion.duration(parameters) * *weight)
+}
+
+/// 按相同的归一化时间采样各动作并按权重混合, 使不同时长的动作保持同步
+pub(crate) fn blend_sample<F: KeyFrameCurveScalar>(motions: &[&EAnimationMotion<F>], weights: &[F], normalized_time: F, parameters: &AnimationParameters<F>, amountcalc: &AnimationAmountCalc<F>) -> AnimationPose<F> {
+    let mut result: Option<AnimationPose<F>> = None;
+    let mut total = F::zero();
+    for (motion, weight) in motions.iter().zip(weights.iter()) {
+        if *weight <= F::zero() {
+            continue;
+        }
+        let pose = motion.sample_pose(normalized_time, parameters, amountcalc);
+        total += *weight;
+        match &mut result {
+            // 逐个向累计结果混合, 混合量为新动作在已累计权重中的占比
+            Some(result) => result.blend(&pose, *weight / total),
+            None => result = Some(pose),
+        }
+    }
+    result.unwrap_or_default()
+}
+
+/// 点 p 相对 pi -> pj 方向的影响: 1 - (p - pi)·(pj - pi) / |pj - pi|²
+fn cartesian_influence<F: KeyFrameCurveScalar>(pi: [F; 2], pj: [F; 2], p: [F; 2]) -> F {
+    let ij = [pj[0] - pi[0], pj[1] - pi[1]];
+    let ip = [p[0] - pi[0], p[1] - pi[1]];
+    let length = dot(ij, ij);
+    if length <= F::zero() {
+        return F::one();
+    }
+    F::one() - dot(ip, ij) / length
+}
+
+/// 极坐标下的影响 - 以 (长度差 / 平均长度, 夹角 * 2) 为坐标计算
+fn directional_influence<F: KeyFrameCurveScalar>(pi: [F; 2], pj: [F; 2], p: [F; 2]) -> F {
+    let two = F::one() + F::one();
+    let (mi, mj, m) = (length(pi), length(pj), length(p));
+    let average = (mi + mj) / two;
+    if average <= F::zero() {
+        return F::one();
+    }
+    let ij = [(mj - mi) / average, angle(pi, pj) * two];
+    let ip = [(m - mi) / average, angle(pi, p) * two];
+    let length = dot(ij, ij);
+    if length <= F::zero() {
+        return F::one();
+    }
+    F::one() - dot(ip, ij) / length
+}
+
+fn dot<F: KeyFrameCurveScalar>(a: [F; 2], b: [F; 2]) -> F {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn length<F: KeyFrameCurveScalar>(a: [F; 2]) -> F {
+    dot(a, a).try_sqrt().unwrap_or(F::zero())
+}
+
+/// a 到 b 的有向夹角, 任一为零向量时为 0
+fn angle<F: KeyFrameCurveScalar>(a: [F; 2], b: [F; 2]) -> F {
+    let lengths = length(a) * length(b);
+    if lengths <= F::zero() {
+        return F::zero();
+    }
+    let angle = (dot(a, b) / lengths).clamp(-F::one(), F::one()).acos();
+    if a[0] * b[1] - a[1] * b[0] < F::zero() { -angle } else { angle }
+}
@@ -0,0 +1,281 @@
+use core::any::{Any, TypeId};
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{amount::AnimationAmountCalc, curve::{frame::{FrameDataValue, FrameValueSlerp, KeyFrameCurveScalar, KeyFrameCurveValue, KeyFrameDataType}, frame_curve::FrameCurve, ErrorCurve, FrameIndex, FramePerSecond}};
+
+use super::pose::{AnimationPose, AnimationValue, MixValue};
+
+/// 擦除数值类型后的曲线, 以便同一剪辑中容纳不同数据类型的曲线
+pub(crate) trait AnimationClipCurve<F: KeyFrameCurveScalar>: Any + Send + Sync {
+    fn max_frame(&self) -> FrameIndex;
+    fn sample(&self, time: F, amountcalc: &AnimationAmountCalc<F>, output: &mut dyn FnMut(&dyn Any));
+    fn sample_value(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> Box<dyn AnimationValue<F>>;
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// 曲线及其值的混合方法
+pub(crate) struct ClipCurve<T: FrameDataValue<F>, F: KeyFrameCurveScalar> {
+    pub curve: FrameCurve<T, F>,
+    pub mix: fn(&T, &T, F) -> T,
+}
+
+impl<T: FrameDataValue<F> + Send + Sync + 'static, F: KeyFrameCurveScalar> AnimationClipCurve<F> for ClipCurve<T, F> {
+    fn max_frame(&self) -> FrameIndex {
+        self.curve.max_frame
+    }
+    fn sample(&self, time: F, amountcalc: &AnimationAmountCalc<F>, output: &mut dyn FnMut(&dyn Any)) {
+        output(&self.curve.interple(time, amountcalc));
+    }
+    fn sample_value(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> Box<dyn AnimationValue<F>> {
+        Box::new(MixValue { value: self.curve.interple(time, amountcalc), mix: self.mix })
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// 剪辑采样结果的接收者
+///
+/// * [target] - 目标路径
+/// * [property] - 目标属性
+/// * [data_type] - 数值类型 ID, 据此将 `value` 转换为具体类型
+/// * [value] - 采样值
+pub trait AnimationClipSink {
+    fn apply(&mut self, target: &str, property: &str, data_type: KeyFrameDataType, value: &dyn Any);
+}
+
+impl<S: FnMut(&str, &str, KeyFrameDataType, &dyn Any)> AnimationClipSink for S {
+    fn apply(&mut self, target: &str, property: &str, data_type: KeyFrameDataType, value: &dyn Any) {
+        self(target, property, data_type, value)
+    }
+}
+
+/// 动画通道 - 一条曲线及其绑定的 目标路径 + 属性
+pub struct AnimationChannel<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    target: String,
+    property: String,
+    data_type: KeyFrameDataType,
+    curve: Box<dyn AnimationClipCurve<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> AnimationChannel<F> {
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+    pub fn property(&self) -> &str {
+        &self.property
+    }
+    pub fn data_type(&self) -> KeyFrameDataType {
+        self.data_type
+    }
+    /// 通道曲线, 类型与添加时不符返回 None
+    pub fn curve<T: FrameDataValue<F> + 'static>(&self) -> Option<&FrameCurve<T, F>> {
+        self.curve.as_any().downcast_ref::<ClipCurve<T, F>>().map(|curve| &curve.curve)
+    }
+}
+
+/// 动画剪辑 - 一组绑定到命名属性的曲线
+/// 所有曲线使用相同的设计帧率, 采样时间单位为秒 (与 `FrameCurve::interple` 一致)
+/// 曲线数值类型须为 Send + Sync (与是否启用 rayon 无关), 剪辑与姿态均可跨线程共享
+pub struct AnimationClip<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    design_frame_per_second: FramePerSecond,
+    max_frame: FrameIndex,
+    channels: Vec<AnimationChannel<F>>,
+    /// 数值类型 ID 对应的具体类型, 保证同一 ID 不会绑定不同类型
+    data_types: BTreeMap<KeyFrameDataType, TypeId>,
+    /// 叠加剪辑的参考姿态, 采样结果为与其的差量
+    reference: Option<AnimationPose<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> AnimationClip<F> {
+    pub fn new(design_frame_per_second: FramePerSecond) -> Self {
+        Self {
+            design_frame_per_second,
+            max_frame: 0,
+            channels: Vec::new(),
+            data_types: BTreeMap::new(),
+            reference: None,
+        }
+    }
+    pub fn design_frame_per_second(&self) -> FramePerSecond {
+        self.design_frame_per_second
+    }
+    /// 结束帧 - 所有曲线中最大的
+    pub fn max_frame(&self) -> FrameIndex {
+        self.max_frame
+    }
+    /// 时长 (秒)
+    pub fn duration(&self) -> F {
+        F::from_frame_index(self.max_frame) / F::from_frame_index(self.design_frame_per_second)
+    }
+    pub fn channels(&self) -> &[AnimationChannel<F>] {
+        &self.channels
+    }
+    pub fn channel(&self, target: &str, property: &str) -> Option<&AnimationChannel<F>> {
+        self.channels.iter().find(|channel| channel.target == target && channel.property == property)
+    }
+    /// 通道曲线, 不存在或类型不符返回 None
+    pub fn curve<T: FrameDataValue<F> + 'static>(&self, target: &str, property: &str) -> Option<&FrameCurve<T, F>> {
+        self.channel(target, property).and_then(|channel| channel.curve())
+    }
+
+    /// 添加曲线
+    ///
+    /// * [target] - 目标路径
+    /// * [property] - 目标属性
+    /// * [data_type] - 数值类型 ID (由 `KeyFrameDataTypeAllocator` 分配), 同一 ID 只能对应一种数值类型
+    /// * [curve] - 曲线, 设计帧率须与剪辑一致
+    ///
+    pub fn add_curve<T: FrameDataValue<F> + Send + Sync + 'static>(
+        &mut self,
+        target: &str,
+        property: &str,
+        data_type: KeyFrameDataType,
+        curve: FrameCurve<T, F>,
+    ) -> Result<(), ErrorCurve> {
+        self.add_curve_with_mix(target, property, data_type, curve, T::interpolate)
+    }
+
+    /// 添加旋转类曲线, 与其他剪辑的值混合时使用 `FrameValueSlerp::slerp`
+    pub fn add_rotation_curve<T: FrameDataValue<F> + FrameValueSlerp<F> + Send + Sync + 'static>(
+        &mut self,
+        target: &str,
+        property: &str,
+        data_type: KeyFrameDataType,
+        curve: FrameCurve<T, F>,
+    ) -> Result<(), ErrorCurve> {
+        self.add_curve_with_mix(target, property, data_type, curve, T::slerp)
+    }
+
+    /// 添加曲线, 并指定与其他剪辑的值混合时的插值方法
+    pub fn add_curve_with_mix<T: FrameDataValue<F> + Send + Sync + 'static>(
+        &mut self,
+        target: &str,
+        property: &str,
+        data_type: KeyFrameDataType,
+        curve: FrameCurve<T, F>,
+        mix: fn(&T, &T, F) -> T,
+    ) -> Result<(), ErrorCurve> {
+        if curve.design_frame_per_second != self.design_frame_per_second {
+            return Err(ErrorCurve::FramePerSecondMismatch);
+        }
+        if self.channel(target, property).is_some() {
+            return Err(ErrorCurve::ChannelAlreadyExists);
+        }
+        let type_id = TypeId::of::<T>();
+        if *self.data_types.entry(data_type).or_insert(type_id) != type_id {
+            return Err(ErrorCurve::KeyFrameDataTypeMismatch);
+        }
+
+        self.max_frame = self.max_frame.max(curve.max_frame);
+        self.channels.push(AnimationChannel {
+            target: String::from(target),
+            property: String::from(property),
+            data_type,
+            curve: Box::new(ClipCurve { curve, mix }),
+        });
+        Ok(())
+    }
+
+    /// 移除通道
+    pub fn remove_curve(&mut self, target: &str, property: &str) -> bool {
+        let len = self.channels.len();
+        self.channels.retain(|channel| channel.target != target || channel.property != property);
+        if self.channels.len() == len {
+            return false;
+        }
+        self.max_frame = self.channels.iter().map(|channel| channel.curve.max_frame()).max().unwrap_or(0);
+        true
+    }
+
+    /// 在指定时间 (秒) 采样所有通道, 结果依次交给 `sink`
+    /// 叠加剪辑输出与参考姿态的差量
+    pub fn sample<S: AnimationClipSink + ?Sized>(&self, time: F, amountcalc: &AnimationAmountCalc<F>, sink: &mut S) {
+        if self.reference.is_some() {
+            self.sample_pose(time, amountcalc).apply(sink);
+            return;
+        }
+        for channel in &self.channels {
+            channel.curve.sample(time, amountcalc, &mut |value| sink.apply(&channel.target, &channel.property, channel.data_type, value));
+        }
+    }
+
+    /// 在指定时间 (秒) 采样所有通道为姿态
+    /// 叠加剪辑输出与参考姿态的差量
+    pub fn sample_pose(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> AnimationPose<F> {
+        let mut pose = AnimationPose::new();
+        for channel in &self.channels {
+            pose.insert(&channel.target, &channel.property, channel.data_type, channel.curve.sample_value(time, amountcalc));
+        }
+        if let Some(reference) = &self.reference {
+            pose.subtract(reference);
+        }
+        pose
+    }
+
+    /// 是否为叠加剪辑
+    pub fn is_additive(&self) -> bool {
+        self.reference.is_some()
+    }
+
+    /// 转换为叠加剪辑, 以自身在 `reference_frame` 帧的姿态为参考
+    /// 例如呼吸动画以第 0 帧为参考, 叠加到行走动画上
+    pub fn into_additive(self, reference_frame: FrameIndex) -> Self {
+        let time = F::from_frame_index(reference_frame) / F::from_frame_index(self.design_frame_per_second);
+        let reference = self.sample_pose(time, &AnimationAmountCalc::default());
+        self.into_additive_with(reference)
+    }
+
+    /// 转换为叠加剪辑, 以给定姿态为参考 (如其他剪辑的某一帧)
+    /// 参考姿态中没有的通道保持原值
+    pub fn into_additive_with(mut self, reference: AnimationPose<F>) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<F: KeyFrameCurveScalar> AnimationClip<F> {
+    /// 在指定时间 (秒) 并行采样所有通道为姿态, 结果与 `sample_pose` 一致
+    /// 叠加剪辑输出与参考姿态的差量
+    pub fn par_sample_pose(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> AnimationPose<F> {
+        use rayon::prelude::*;
+
+        let values: Vec<Box<dyn AnimationValue<F>>> = self.channels.par_iter()
+            .with_min_len(crate::curve::parallel::PAR_MIN_LEN)
+            .map(|channel| channel.curve.sample_value(time, amountcalc))
+            .collect();
+        let mut pose = AnimationPose::new();
+        for (channel, value) in self.channels.iter().zip(values) {
+            pose.insert(&channel.target, &channel.property, channel.data_type, value);
+        }
+        if let Some(reference) = &self.reference {
+            pose.subtract(reference);
+        }
+        pose
+    }
+
+    /// 在指定时间 (秒) 并行采样数值类型为 T 的所有通道, 按通道顺序写入 output
+    /// output 长度须与此类通道数量一致; 叠加剪辑输出与参考姿态的差量
+    /// 直接写入具体类型, 不像 `par_sample_pose` 为每个值分配并擦除类型
+    pub fn par_sample_type<T: FrameDataValue<F> + Send + Sync + 'static>(&self, time: F, amountcalc: &AnimationAmountCalc<F>, output: &mut [T]) -> Result<(), ErrorCurve> {
+        use rayon::prelude::*;
+
+        let curves: Vec<(&FrameCurve<T, F>, Option<&T>)> = self.channels.iter().filter_map(|channel| {
+            let reference = self.reference.as_ref().and_then(|reference| reference.get::<T>(&channel.target, &channel.property));
+            channel.curve::<T>().map(|curve| (curve, reference))
+        }).collect();
+        if curves.len() != output.len() {
+            return Err(ErrorCurve::ChannelCountMismatch);
+        }
+        output.par_iter_mut().with_min_len(crate::curve::parallel::PAR_MIN_LEN).zip(curves.par_iter()).for_each(|(result, (curve, reference))| {
+            let value = curve.interple(time, amountcalc);
+            *result = match reference {
+                Some(reference) => value.delta(reference),
+                None => value,
+            };
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::vec::Vec;
+
+use crate::curve::{frame::KeyFrameCurveScalar, FrameIndex, FramePerSecond};
+
+use super::player::AnimationPlayer;
+
+/// `crossed` 中起止之间完整经过的迭代最多返回的次数
+pub const MAX_CROSSED_ITERATIONS: u64 = 1024;
+
+/// 动画事件 - 绑定到帧的数据
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationEvent<E> {
+    pub frame: FrameIndex,
+    pub payload: E,
+}
+
+/// 动画事件轨道
+/// 事件按帧排序, 同一帧的事件保持添加顺序
+#[derive(Debug, Clone)]
+pub struct AnimationEventTrack<E> {
+    design_frame_per_second: FramePerSecond,
+    events: Vec<AnimationEvent<E>>,
+}
+
+impl<E> AnimationEventTrack<E> {
+    pub fn new(design_frame_per_second: FramePerSecond) -> Self {
+        Self {
+            design_frame_per_second,
+            events: Vec::new(),
+        }
+    }
+    pub fn design_frame_per_second(&self) -> FramePerSecond {
+        self.design_frame_per_second
+    }
+    pub fn events(&self) -> &[AnimationEvent<E>] {
+        &self.events
+    }
+    pub fn add(&mut self, frame: FrameIndex, payload: E) {
+        let index = self.events.partition_point(|event| event.frame <= frame);
+        self.events.insert(index, AnimationEvent { frame, payload });
+    }
+    /// 移除满足条件的事件, 返回移除数目
+    pub fn remove<P: FnMut(&AnimationEvent<E>) -> bool>(&mut self, mut predicate: P) -> usize {
+        let len = self.events.len();
+        self.events.retain(|event| !predicate(event));
+        len - self.events.len()
+    }
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    /// 播放器时间从 `from` 变化到 `to` 时越过的事件, 按越过的先后排列
+    ///
+    /// * 按播放器的延迟/迭代次数/播放方向将时间映射到各次迭代, 跨越多次迭代时每次迭代的事件都会返回
+    /// * 起点不计入, 终点计入; 从动画开头 (正向) 或结尾 (反向) 出发时起点计入,
+    ///   因此首尾相接的连续查询不会遗漏或重复事件
+    /// * 迭代交界处先到达上一次迭代的末尾, 再到达下一次迭代的开头
+    /// * 起止之间完整经过的迭代最多返回 [`MAX_CROSSED_ITERATIONS`] 次 (靠近终点的部分), 其余跳过,
+    ///   避免一次大跨度跳转产生无上限的事件
+    ///
+    pub fn crossed<F: KeyFrameCurveScalar>(&self, player: &AnimationPlayer<F>, from: F, to: F) -> Vec<&AnimationEvent<E>> {
+        let mut result = Vec::new();
+        let duration = player.duration();
+        if self.events.is_empty() || duration <= F::zero() {
+            return result;
+        }
+
+        let active = player.active_duration();
+        let clamp = |time: F| {
+            let time = (time - player.delay).max(F::zero());
+            match active {
+                Some(active) => time.min(active),
+                None => time,
+            }
+        };
+        let (start, end) = (clamp(from), clamp(to));
+        if start == end {
+            return result;
+        }
+        let forward = end > start;
+        let include_start = if forward { start == F::zero() } else { Some(start) == active };
+
+        let (start_iteration, start_local) = position(start, duration, active);
+        let (end_iteration, end_local) = position(end, duration, active);
+        // 起止之间完整经过的迭代超出上限的部分直接跳过, 只保留靠近终点的迭代
+        let skipped = start_iteration.abs_diff(end_iteration).saturating_sub(1).saturating_sub(MAX_CROSSED_ITERATIONS);
+
+        let fps = F::from_frame_index(self.design_frame_per_second);
+        let mut iteration = start_iteration;
+        loop {
+            let first = iteration == start_iteration;
+            let last = iteration == end_iteration;
+            // 本次迭代内经过的区间 (迭代内时间, 按播放先后)
+            let (from_local, to_local) = (
+                if first { start_local } else if forward { F::zero() } else { duration },
+                if last { end_local } else if forward { duration } else { F::zero() },
+            );
+            let from_inclusive = !first || include_start;
+
+            let reversed = player.is_reversed_iteration_index(iteration);
+            let crossed = |event: &AnimationEvent<E>| {
+                let time = F::from_frame_index(event.frame) / fps;
+                let local = if reversed { duration - time } else { time };
+                if forward {
+                    (local > from_local || (from_inclusive && local == from_local)) && local <= to_local
+                } else {
+                    (local < from_local || (from_inclusive && local == from_local)) && local >= to_local
+                }
+            };
+            // 迭代内进度递增时, 正向迭代按帧升序, 反向迭代按帧降序
+            if forward != reversed {
+                result.extend(self.events.iter().filter(|event| crossed(event)));
+            } else {
+                result.extend(self.events.iter().rev().filter(|event| crossed(event)));
+            }
+
+            if last {
+                break;
+            }
+            let step = if first { 1 + skipped } else { 1 };
+            iteration = if forward { iteration + step } else { iteration - step };
+        }
+
+        result
+    }
+}
+
+/// 激活时间所在 (迭代序号, 迭代内时间)
+/// 迭代交界处视为下一次迭代的开头, 但总时长末尾视为最后一次迭代的末尾
+fn position<F: KeyFrameCurveScalar>(time: F, duration: F, active: Option<F>) -> (u64, F) {
+    let iteration = (time / duration).floor();
+    let local = time - iteration * duration;
+    // 超出 u64 范围时饱和
+    let index = iteration.to_f64_value() as u64;
+    if local == F::zero() && index > 0 && Some(time) == active {
+        (index - 1, duration)
+    } else {
+        (index, local)
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{amount::AnimationAmountCalc, curve::frame::{KeyFrameCurveScalar, KeyFrameCurveValue}};
+
+use super::{clip::AnimationClip, player::AnimationPlayer, pose::AnimationPose};
+
+/// 层遮罩 - 目标路径对应的权重
+/// 目标路径以 '/' 分隔, 未设置的路径取最近的上级路径的权重, 都没有则为 0
+#[derive(Debug, Clone)]
+pub struct AnimationMask<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    targets: BTreeMap<String, F>,
+}
+
+impl<F: KeyFrameCurveScalar> Default for AnimationMask<F> {
+    fn default() -> Self {
+        Self { targets: BTreeMap::new() }
+    }
+}
+
+impl<F: KeyFrameCurveScalar> AnimationMask<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 设置目标路径 (及其下级路径) 的权重
+    pub fn set(&mut self, target: &str, weight: F) {
+        self.targets.insert(String::from(target), weight);
+    }
+    pub fn weight(&self, target: &str) -> F {
+        let mut path = target;
+        loop {
+            if let Some(weight) = self.targets.get(path) {
+                return *weight;
+            }
+            match path.rfind('/') {
+                Some(index) => path = &path[..index],
+                None => return F::zero(),
+            }
+        }
+    }
+}
+
+/// 动画层
+/// 剪辑为叠加剪辑 (`AnimationClip::into_additive`) 时为叠加层, 否则为覆盖层
+pub struct AnimationLayer<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub clip: AnimationClip<F>,
+    pub player: AnimationPlayer<F>,
+    pub amountcalc: AnimationAmountCalc<F>,
+    /// 层权重
+    pub weight: F,
+    /// 层遮罩, None 时作用于所有目标
+    pub mask: Option<AnimationMask<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> AnimationLayer<F> {
+    /// 以剪辑时长创建播放器, 权重为 1, 无遮罩
+    pub fn new(clip: AnimationClip<F>) -> Self {
+        Self {
+            player: AnimationPlayer::from_clip(&clip),
+            clip,
+            amountcalc: AnimationAmountCalc::default(),
+            weight: F::one(),
+            mask: None,
+        }
+    }
+    pub fn is_additive(&self) -> bool {
+        self.clip.is_additive()
+    }
+    /// 目标路径的有效权重
+    pub fn target_weight(&self, target: &str) -> F {
+        match &self.mask {
+            Some(mask) => self.weight * mask.weight(target),
+            None => self.weight,
+        }
+    }
+}
+
+/// 动画层栈 - 按顺序应用覆盖层与叠加层
+pub struct AnimationLayerStack<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    layers: Vec<AnimationLayer<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> Default for AnimationLayerStack<F> {
+    fn default() -> Self {
+        Self { layers: Vec::new() }
+    }
+}
+
+impl<F: KeyFrameCurveScalar> AnimationLayerStack<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 添加层, 返回层序号
+    pub fn push(&mut self, layer: AnimationLayer<F>) -> usize {
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+    pub fn layers(&self) -> &[AnimationLayer<F>] {
+        &self.layers
+    }
+    pub fn layer(&self, index: usize) -> Option<&AnimationLayer<F>> {
+        self.layers.get(index)
+    }
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut AnimationLayer<F>> {
+        self.layers.get_mut(index)
+    }
+    /// 推进所有层的播放时间
+    pub fn tick(&mut self, delta: F) {
+        for layer in &mut self.layers {
+            layer.player.tick(delta);
+        }
+    }
+
+    /// 计算各层叠加后的姿态
+    ///
+    /// * 覆盖层: 按权重向该层的值混合, 下层没有的值直接使用该层的值
+    /// * 叠加层: 加上该层差量 * 权重, 下层没有的值忽略
+    /// * 播放器未生效的层被跳过
+    ///
+    pub fn evaluate(&self) -> AnimationPose<F> {
+        let mut pose = AnimationPose::new();
+        for layer in &self.layers {
+            if layer.weight <= F::zero() {
+                continue;
+            }
+            let time = match layer.player.iteration_time() {
+                Some(time) => time,
+                None => continue,
+            };
+            let layer_pose = layer.clip.sample_pose(time, &layer.amountcalc);
+            pose.layer(layer_pose, layer.is_additive(), |target| layer.target_weight(target));
+        }
+        pose
+    }
+}
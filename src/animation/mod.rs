@@ -0,0 +1,13 @@
+//!
+//! 动画数据组织
+
+pub mod clip;
+pub mod player;
+pub mod event;
+pub mod blend;
+pub mod pose;
+pub mod layer;
+pub mod state_machine;
+pub mod blend_tree;
+pub mod timeline;
+pub mod tween;
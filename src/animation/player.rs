@@ -0,0 +1,259 @@
+use crate::{amount::AnimationAmountCalc, curve::{frame::{FrameDataValue, KeyFrameCurveScalar, KeyFrameCurveValue}, frame_curve::FrameCurve, FrameIndex, FramePerSecond}};
+
+use super::clip::{AnimationClip, AnimationClipSink};
+
+/// 播放方向 (CSS animation-direction)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EPlaybackDirection {
+    #[default]
+    Normal,
+    Reverse,
+    /// 奇数次迭代正向, 偶数次反向
+    Alternate,
+    /// 奇数次迭代反向, 偶数次正向
+    AlternateReverse,
+}
+
+/// 填充模式 (CSS animation-fill-mode) - 动画未生效时是否保持首/尾值
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EFillMode {
+    #[default]
+    None,
+    /// 结束后保持最后的值
+    Forwards,
+    /// 延迟期间使用起始值
+    Backwards,
+    Both,
+}
+
+/// 迭代次数, 可为小数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EIterationCount<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    Count(F),
+    Infinite,
+}
+
+/// 播放阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EAnimationPhase {
+    /// 延迟中 (或反向播放越过起点)
+    Before,
+    Active,
+    /// 已播放完成
+    After,
+}
+
+/// 动画播放器 - 记录播放时间, 按 Web Animations 计时模型计算迭代进度
+/// 时间单位为秒, 与 `FrameCurve::interple` 一致
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationPlayer<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    /// 单次迭代时长
+    duration: F,
+    /// 开始播放前的延迟
+    pub delay: F,
+    /// 播放速率, 负数反向推进时间
+    pub playback_rate: F,
+    pub iterations: EIterationCount<F>,
+    pub direction: EPlaybackDirection,
+    pub fill: EFillMode,
+    /// 播放器时间 (包含延迟)
+    current_time: F,
+}
+
+impl<F: KeyFrameCurveScalar> AnimationPlayer<F> {
+    pub fn new(duration: F) -> Self {
+        Self {
+            duration: duration.max(F::zero()),
+            delay: F::zero(),
+            playback_rate: F::one(),
+            iterations: EIterationCount::Count(F::one()),
+            direction: EPlaybackDirection::Normal,
+            fill: EFillMode::None,
+            current_time: F::zero(),
+        }
+    }
+    /// 以曲线结束帧为单次迭代时长
+    pub fn from_curve<T: FrameDataValue<F>>(curve: &FrameCurve<T, F>) -> Self {
+        Self::new(frame_duration(curve.max_frame, curve.design_frame_per_second))
+    }
+    /// 以剪辑时长为单次迭代时长
+    pub fn from_clip(clip: &AnimationClip<F>) -> Self {
+        Self::new(clip.duration())
+    }
+    pub fn duration(&self) -> F {
+        self.duration
+    }
+    pub fn set_duration(&mut self, duration: F) {
+        self.duration = duration.max(F::zero());
+    }
+    pub fn current_time(&self) -> F {
+        self.current_time
+    }
+    /// 跳转到指定时间
+    pub fn seek(&mut self, time: F) {
+        self.current_time = time;
+    }
+    /// 推进时间, 实际推进 `delta * playback_rate`
+    pub fn tick(&mut self, delta: F) {
+        self.current_time += delta * self.playback_rate;
+    }
+    /// 回到播放起点 - 正向播放为 0, 反向播放为结束时间
+    /// 无限迭代时无结束时间, 反向播放起点仍为 0
+    pub fn restart(&mut self) {
+        self.current_time = match (self.playback_rate < F::zero(), self.end_time()) {
+            (true, Some(end)) => end,
+            _ => F::zero(),
+        };
+    }
+
+    /// 所有迭代的总时长, 无限迭代返回 None
+    pub fn active_duration(&self) -> Option<F> {
+        match self.iterations {
+            EIterationCount::Count(count) => Some(self.duration * count.max(F::zero())),
+            EIterationCount::Infinite => None,
+        }
+    }
+    /// 结束时间 (延迟 + 总时长), 无限迭代返回 None
+    pub fn end_time(&self) -> Option<F> {
+        self.active_duration().map(|active| (self.delay + active).max(F::zero()))
+    }
+
+    pub fn phase(&self) -> EAnimationPhase {
+        let before_active = match self.end_time() {
+            Some(end) => self.delay.min(end),
+            None => self.delay,
+        }.max(F::zero());
+
+        let backwards = self.playback_rate < F::zero();
+        if self.current_time < before_active || (backwards && self.current_time == before_active) {
+            return EAnimationPhase::Before;
+        }
+        if let (Some(end), Some(active)) = (self.end_time(), self.active_duration()) {
+            let active_after = (self.delay + active).min(end).max(F::zero());
+            if self.current_time > active_after || (!backwards && self.current_time == active_after) {
+                return EAnimationPhase::After;
+            }
+        }
+        EAnimationPhase::Active
+    }
+
+    /// 正向播放已越过结束点, 或反向播放已越过起点
+    pub fn is_finished(&self) -> bool {
+        match self.phase() {
+            EAnimationPhase::After => self.playback_rate >= F::zero(),
+            EAnimationPhase::Before => self.playback_rate < F::zero() && self.current_time <= F::zero(),
+            EAnimationPhase::Active => false,
+        }
+    }
+
+    /// 减去延迟后的时间, 动画未生效返回 None
+    pub fn active_time(&self) -> Option<F> {
+        let local = self.current_time - self.delay;
+        match self.phase() {
+            EAnimationPhase::Before => match self.fill {
+                EFillMode::Backwards | EFillMode::Both => Some(local.max(F::zero())),
+                _ => None,
+            },
+            EAnimationPhase::Active => Some(local),
+            EAnimationPhase::After => match self.fill {
+                EFillMode::Forwards | EFillMode::Both => {
+                    // After 阶段必然有限次迭代
+                    let active = self.active_duration().unwrap_or(local);
+                    Some(local.min(active).max(F::zero()))
+                },
+                _ => None,
+            },
+        }
+    }
+
+    /// 当前迭代序号 (从 0 开始), 动画未生效返回 None
+    pub fn current_iteration(&self) -> Option<F> {
+        self.iteration_state().map(|(iteration, _)| iteration)
+    }
+
+    /// 当前迭代内的进度 [0, 1], 已按播放方向翻转, 动画未生效返回 None
+    pub fn progress(&self) -> Option<F> {
+        let (iteration, simple) = self.iteration_state()?;
+        Some(if self.is_reversed_iteration(iteration) { F::one() - simple } else { simple })
+    }
+
+    /// 当前迭代内的时间 (秒), 动画未生效返回 None
+    pub fn iteration_time(&self) -> Option<F> {
+        self.progress().map(|progress| progress * self.duration)
+    }
+
+    /// 采样曲线, 动画未生效返回 None
+    pub fn sample<T: FrameDataValue<F>>(&self, curve: &FrameCurve<T, F>, amountcalc: &AnimationAmountCalc<F>) -> Option<T> {
+        self.iteration_time().map(|time| curve.interple(time, amountcalc))
+    }
+
+    /// 采样剪辑, 动画未生效时不输出并返回 false
+    pub fn sample_clip<S: AnimationClipSink + ?Sized>(&self, clip: &AnimationClip<F>, amountcalc: &AnimationAmountCalc<F>, sink: &mut S) -> bool {
+        match self.iteration_time() {
+            Some(time) => {
+                clip.sample(time, amountcalc, sink);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// 指定序号的迭代是否反向播放
+    pub(crate) fn is_reversed_iteration(&self, iteration: F) -> bool {
+        self.is_reversed_parity(is_odd(iteration))
+    }
+    /// 同 `is_reversed_iteration`, 以整数计数迭代, 不受浮点精度限制
+    pub(crate) fn is_reversed_iteration_index(&self, iteration: u64) -> bool {
+        self.is_reversed_parity(iteration % 2 == 1)
+    }
+    fn is_reversed_parity(&self, odd: bool) -> bool {
+        match self.direction {
+            EPlaybackDirection::Normal => false,
+            EPlaybackDirection::Reverse => true,
+            EPlaybackDirection::Alternate => odd,
+            EPlaybackDirection::AlternateReverse => !odd,
+        }
+    }
+
+    /// (当前迭代序号, 未翻转的迭代内进度)
+    fn iteration_state(&self) -> Option<(F, F)> {
+        let active_time = self.active_time()?;
+        let phase = self.phase();
+        let count = match self.iterations {
+            EIterationCount::Count(count) => count.max(F::zero()),
+            // 无限迭代不会进入 After 阶段, 仅 duration 为 0 时使用
+            EIterationCount::Infinite => F::one(),
+        };
+
+        let overall = if self.duration == F::zero() {
+            if phase == EAnimationPhase::Before { F::zero() } else { count }
+        } else {
+            active_time / self.duration
+        };
+
+        let mut simple = overall - overall.floor();
+        let at_end = match self.active_duration() {
+            Some(active) => active_time == active,
+            None => false,
+        };
+        if simple == F::zero() && phase != EAnimationPhase::Before && at_end && count != F::zero() {
+            simple = F::one();
+        }
+
+        let iteration = if simple == F::one() {
+            overall.floor() - F::one()
+        } else {
+            overall.floor()
+        };
+        Some((iteration.max(F::zero()), simple))
+    }
+}
+
+fn frame_duration<F: KeyFrameCurveScalar>(frame: FrameIndex, design_frame_per_second: FramePerSecond) -> F {
+    F::from_frame_index(frame) / F::from_frame_index(design_frame_per_second)
+}
+
+fn is_odd<F: KeyFrameCurveScalar>(iteration: F) -> bool {
+    let two = F::one() + F::one();
+    iteration - (iteration / two).floor() * two >= F::one()
+}
@@ -0,0 +1,204 @@
+use core::any::Any;
+
+use alloc::{boxed::Box, collections::BTreeMap, string::String};
+
+use crate::curve::frame::{FrameDataValue, KeyFrameCurveScalar, KeyFrameCurveValue, KeyFrameDataType};
+
+use super::clip::AnimationClipSink;
+
+/// 擦除类型的动画值, 保留混合所需的运算
+pub(crate) trait AnimationValue<F: KeyFrameCurveScalar>: Any + Send + Sync {
+    /// 具体类型的值
+    fn as_any(&self) -> &dyn Any;
+    fn clone_value(&self) -> Box<dyn AnimationValue<F>>;
+    /// self 向 rhs 混合 amount, 类型不符时不变
+    fn blend(&mut self, rhs: &dyn AnimationValue<F>, amount: F);
+    /// 叠加差量 rhs * amount, 类型不符时不变
+    fn add_delta(&mut self, rhs: &dyn AnimationValue<F>, amount: F);
+    /// 转为相对 reference 的差量, 类型不符时不变
+    fn delta(&mut self, reference: &dyn AnimationValue<F>);
+}
+
+/// 动画值及其混合方法 (插值 或 球面插值)
+pub(crate) struct MixValue<T: FrameDataValue<F>, F: KeyFrameCurveScalar> {
+    pub value: T,
+    pub mix: fn(&T, &T, F) -> T,
+}
+
+impl<T: FrameDataValue<F> + Send + Sync + 'static, F: KeyFrameCurveScalar> AnimationValue<F> for MixValue<T, F> {
+    fn as_any(&self) -> &dyn Any {
+        &self.value
+    }
+    fn clone_value(&self) -> Box<dyn AnimationValue<F>> {
+        Box::new(MixValue { value: self.value.clone(), mix: self.mix })
+    }
+    fn blend(&mut self, rhs: &dyn AnimationValue<F>, amount: F) {
+        if let Some(rhs) = rhs.as_any().downcast_ref::<T>() {
+            self.value = (self.mix)(&self.value, rhs, amount);
+        }
+    }
+    fn add_delta(&mut self, rhs: &dyn AnimationValue<F>, amount: F) {
+        if let Some(rhs) = rhs.as_any().downcast_ref::<T>() {
+            self.value = self.value.add_delta(rhs, amount);
+        }
+    }
+    fn delta(&mut self, reference: &dyn AnimationValue<F>) {
+        if let Some(reference) = reference.as_any().downcast_ref::<T>() {
+            self.value = self.value.delta(reference);
+        }
+    }
+}
+
+struct PoseValue<F: KeyFrameCurveScalar> {
+    data_type: KeyFrameDataType,
+    value: Box<dyn AnimationValue<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> Clone for PoseValue<F> {
+    fn clone(&self) -> Self {
+        Self { data_type: self.data_type, value: self.value.clone_value() }
+    }
+}
+
+/// 动画姿态 - 一组 目标路径 + 属性 对应的采样值
+/// 按 目标路径, 属性 排序
+#[derive(Clone)]
+pub struct AnimationPose<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    targets: BTreeMap<String, BTreeMap<String, PoseValue<F>>>,
+}
+
+impl<F: KeyFrameCurveScalar> Default for AnimationPose<F> {
+    fn default() -> Self {
+        Self { targets: BTreeMap::new() }
+    }
+}
+
+impl<F: KeyFrameCurveScalar> AnimationPose<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn len(&self) -> usize {
+        self.targets.values().map(|properties| properties.len()).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+    pub fn clear(&mut self) {
+        self.targets.clear();
+    }
+    pub fn contains(&self, target: &str, property: &str) -> bool {
+        self.entry(target, property).is_some()
+    }
+    /// (数值类型 ID, 值)
+    pub fn value(&self, target: &str, property: &str) -> Option<(KeyFrameDataType, &dyn Any)> {
+        self.entry(target, property).map(|entry| (entry.data_type, entry.value.as_any()))
+    }
+    /// 指定类型的值, 不存在或类型不符返回 None
+    pub fn get<T: 'static>(&self, target: &str, property: &str) -> Option<&T> {
+        self.entry(target, property).and_then(|entry| entry.value.as_any().downcast_ref())
+    }
+    /// 设置值, 使用 `FrameDataValue::interpolate` 混合
+    pub fn set<T: FrameDataValue<F> + Send + Sync + 'static>(&mut self, target: &str, property: &str, data_type: KeyFrameDataType, value: T) {
+        self.insert(target, property, data_type, Box::new(MixValue { value, mix: T::interpolate }));
+    }
+    /// 移除值
+    pub fn remove(&mut self, target: &str, property: &str) -> bool {
+        let properties = match self.targets.get_mut(target) {
+            Some(properties) => properties,
+            None => return false,
+        };
+        let removed = properties.remove(property).is_some();
+        if properties.is_empty() {
+            self.targets.remove(target);
+        }
+        removed
+    }
+    /// 仅保留满足条件的值
+    pub fn retain<P: FnMut(&str, &str) -> bool>(&mut self, mut predicate: P) {
+        self.targets.retain(|target, properties| {
+            properties.retain(|property, _| predicate(target, property));
+            !properties.is_empty()
+        });
+    }
+
+    /// 依次将所有值交给 `sink`
+    pub fn apply<S: AnimationClipSink + ?Sized>(&self, sink: &mut S) {
+        for (target, properties) in &self.targets {
+            for (property, entry) in properties {
+                sink.apply(target, property, entry.data_type, entry.value.as_any());
+            }
+        }
+    }
+
+    /// 向另一姿态混合 - 双方都有的值按 amount 混合, 仅对方有的值直接取对方的值
+    pub fn blend(&mut self, other: &AnimationPose<F>, amount: F) {
+        for (target, properties) in &other.targets {
+            for (property, entry) in properties {
+                match self.entry_mut(target, property) {
+                    Some(value) => value.value.blend(entry.value.as_ref(), amount),
+                    None => self.insert(target, property, entry.data_type, entry.value.clone_value()),
+                }
+            }
+        }
+    }
+
+    /// 叠加差量姿态 - 按 `FrameDataValue::add_delta` 叠加 delta * weight, 仅处理双方都有的值
+    pub fn add(&mut self, delta: &AnimationPose<F>, weight: F) {
+        for (target, properties) in &delta.targets {
+            for (property, entry) in properties {
+                if let Some(value) = self.entry_mut(target, property) {
+                    value.value.add_delta(entry.value.as_ref(), weight);
+                }
+            }
+        }
+    }
+
+    /// 减去参考姿态, 按 `FrameDataValue::delta` 得到差量姿态, 仅处理双方都有的值
+    pub fn subtract(&mut self, reference: &AnimationPose<F>) {
+        for (target, properties) in &reference.targets {
+            for (property, entry) in properties {
+                if let Some(value) = self.entry_mut(target, property) {
+                    value.value.delta(entry.value.as_ref());
+                }
+            }
+        }
+    }
+
+    pub(crate) fn insert(&mut self, target: &str, property: &str, data_type: KeyFrameDataType, value: Box<dyn AnimationValue<F>>) {
+        let properties = match self.targets.get_mut(target) {
+            Some(properties) => properties,
+            None => self.targets.entry(String::from(target)).or_default(),
+        };
+        properties.insert(String::from(property), PoseValue { data_type, value });
+    }
+
+    /// 按层叠加另一姿态
+    ///
+    /// * [additive] - false: 覆盖层, 向层的值混合权重; 自身没有的值直接取层的值
+    ///   true: 叠加层, 按 `FrameDataValue::add_delta` 叠加 层的值 * 权重; 自身没有的值忽略
+    /// * [weight] - 目标路径对应的权重, <= 0 的目标被忽略
+    ///
+    pub(crate) fn layer(&mut self, other: AnimationPose<F>, additive: bool, weight: impl Fn(&str) -> F) {
+        for (target, properties) in other.targets {
+            let amount = weight(&target);
+            if amount <= F::zero() {
+                continue;
+            }
+            for (property, entry) in properties {
+                match self.entry_mut(&target, &property) {
+                    Some(value) if additive => value.value.add_delta(entry.value.as_ref(), amount),
+                    Some(value) => value.value.blend(entry.value.as_ref(), amount),
+                    None if additive => {},
+                    None => self.insert(&target, &property, entry.data_type, entry.value),
+                }
+            }
+        }
+    }
+
+    fn entry(&self, target: &str, property: &str) -> Option<&PoseValue<F>> {
+        self.targets.get(target).and_then(|properties| properties.get(property))
+    }
+    fn entry_mut(&mut self, target: &str, property: &str) -> Option<&mut PoseValue<F>> {
+        self.targets.get_mut(target).and_then(|properties| properties.get_mut(property))
+    }
+}
@@ -0,0 +1,380 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{amount::AnimationAmountCalc, curve::frame::{KeyFrameCurveScalar, KeyFrameCurveValue}, easing::EEasingMode};
+
+use super::{blend_tree::{blend_duration, blend_sample, BlendTree1D, BlendTree2D}, clip::AnimationClip, pose::AnimationPose};
+
+/// 动画参数值
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EAnimationParameter<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    Float(F),
+    Bool(bool),
+    /// 触发器 - 被转换使用后自动复位
+    Trigger(bool),
+}
+
+/// 动画参数表
+#[derive(Debug, Clone)]
+pub struct AnimationParameters<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    values: BTreeMap<String, EAnimationParameter<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> Default for AnimationParameters<F> {
+    fn default() -> Self {
+        Self { values: BTreeMap::new() }
+    }
+}
+
+impl<F: KeyFrameCurveScalar> AnimationParameters<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn get(&self, name: &str) -> Option<EAnimationParameter<F>> {
+        self.values.get(name).copied()
+    }
+    pub fn set_float(&mut self, name: &str, value: F) {
+        self.values.insert(String::from(name), EAnimationParameter::Float(value));
+    }
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.values.insert(String::from(name), EAnimationParameter::Bool(value));
+    }
+    pub fn set_trigger(&mut self, name: &str) {
+        self.values.insert(String::from(name), EAnimationParameter::Trigger(true));
+    }
+    pub fn reset_trigger(&mut self, name: &str) {
+        if let Some(EAnimationParameter::Trigger(value)) = self.values.get_mut(name) {
+            *value = false;
+        }
+    }
+    /// 浮点参数, 不存在或类型不符返回 None
+    pub fn float(&self, name: &str) -> Option<F> {
+        match self.get(name) {
+            Some(EAnimationParameter::Float(value)) => Some(value),
+            _ => None,
+        }
+    }
+    /// 布尔参数, 不存在或类型不符返回 None
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.get(name) {
+            Some(EAnimationParameter::Bool(value)) => Some(value),
+            _ => None,
+        }
+    }
+    /// 触发器是否已触发
+    pub fn trigger(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(EAnimationParameter::Trigger(true)))
+    }
+}
+
+/// 转换条件, 参数不存在或类型不符时不满足
+#[derive(Debug, Clone, PartialEq)]
+pub enum EAnimationCondition<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    /// 浮点参数大于给定值
+    Greater(String, F),
+    /// 浮点参数小于给定值
+    Less(String, F),
+    /// 布尔参数为 true
+    True(String),
+    /// 布尔参数为 false
+    False(String),
+    /// 触发器已触发
+    Trigger(String),
+}
+
+impl<F: KeyFrameCurveScalar> EAnimationCondition<F> {
+    pub fn check(&self, parameters: &AnimationParameters<F>) -> bool {
+        match self {
+            EAnimationCondition::Greater(name, value) => parameters.float(name).is_some_and(|param| param > *value),
+            EAnimationCondition::Less(name, value) => parameters.float(name).is_some_and(|param| param < *value),
+            EAnimationCondition::True(name) => parameters.bool(name) == Some(true),
+            EAnimationCondition::False(name) => parameters.bool(name) == Some(false),
+            EAnimationCondition::Trigger(name) => parameters.trigger(name),
+        }
+    }
+}
+
+/// 状态播放的动作
+pub enum EAnimationMotion<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    Clip(AnimationClip<F>),
+    BlendTree1D(BlendTree1D<F>),
+    BlendTree2D(BlendTree2D<F>),
+}
+
+impl<F: KeyFrameCurveScalar> EAnimationMotion<F> {
+    /// 单次播放时长 (秒), 混合树为各动作时长按权重加权
+    pub fn duration(&self, parameters: &AnimationParameters<F>) -> F {
+        match self {
+            EAnimationMotion::Clip(clip) => clip.duration(),
+            EAnimationMotion::BlendTree1D(tree) => {
+                let (motions, weights) = tree.motions_weights(parameters);
+                blend_duration(&motions, &weights, parameters)
+            },
+            EAnimationMotion::BlendTree2D(tree) => {
+                let (motions, weights) = tree.motions_weights(parameters);
+                blend_duration(&motions, &weights, parameters)
+            },
+        }
+    }
+    /// 按归一化时间 [0, 1] 采样
+    pub fn sample_pose(&self, normalized_time: F, parameters: &AnimationParameters<F>, amountcalc: &AnimationAmountCalc<F>) -> AnimationPose<F> {
+        match self {
+            EAnimationMotion::Clip(clip) => clip.sample_pose(normalized_time * clip.duration(), amountcalc),
+            EAnimationMotion::BlendTree1D(tree) => {
+                let (motions, weights) = tree.motions_weights(parameters);
+                blend_sample(&motions, &weights, normalized_time, parameters, amountcalc)
+            },
+            EAnimationMotion::BlendTree2D(tree) => {
+                let (motions, weights) = tree.motions_weights(parameters);
+                blend_sample(&motions, &weights, normalized_time, parameters, amountcalc)
+            },
+        }
+    }
+}
+
+/// 状态转换
+#[derive(Debug, Clone)]
+pub struct AnimationTransition<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    /// 目标状态序号
+    pub to: usize,
+    /// 所有条件满足时转换
+    pub conditions: Vec<EAnimationCondition<F>>,
+    /// 退出时间 - 源状态归一化时间 (循环状态累计计算, 如 1.5 为第二次播放过半) 达到该值后才允许转换
+    /// 没有条件的转换必须设置退出时间
+    pub exit_time: Option<F>,
+    /// 淡入淡出时长 (秒)
+    pub duration: F,
+    /// 淡入淡出进度的缓动
+    pub easing: EEasingMode,
+}
+
+impl<F: KeyFrameCurveScalar> AnimationTransition<F> {
+    pub fn new(to: usize, duration: F) -> Self {
+        Self {
+            to,
+            conditions: Vec::new(),
+            exit_time: None,
+            duration,
+            easing: EEasingMode::None,
+        }
+    }
+    pub fn with_condition(mut self, condition: EAnimationCondition<F>) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+    pub fn with_exit_time(mut self, exit_time: F) -> Self {
+        self.exit_time = Some(exit_time);
+        self
+    }
+    pub fn with_easing(mut self, easing: EEasingMode) -> Self {
+        self.easing = easing;
+        self
+    }
+    fn can_transit(&self, normalized_time: F, parameters: &AnimationParameters<F>) -> bool {
+        if self.exit_time.is_none() && self.conditions.is_empty() {
+            return false;
+        }
+        if let Some(exit_time) = self.exit_time {
+            if normalized_time < exit_time {
+                return false;
+            }
+        }
+        self.conditions.iter().all(|condition| condition.check(parameters))
+    }
+}
+
+/// 动画状态
+pub struct AnimationState<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub name: String,
+    pub motion: EAnimationMotion<F>,
+    /// 播放速度
+    pub speed: F,
+    /// 是否循环播放, 否则停在结尾
+    pub looping: bool,
+    pub amountcalc: AnimationAmountCalc<F>,
+    /// 按顺序检查, 使用第一个满足的转换
+    pub transitions: Vec<AnimationTransition<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> AnimationState<F> {
+    pub fn new(name: &str, motion: EAnimationMotion<F>) -> Self {
+        Self {
+            name: String::from(name),
+            motion,
+            speed: F::one(),
+            looping: true,
+            amountcalc: AnimationAmountCalc::default(),
+            transitions: Vec::new(),
+        }
+    }
+    fn sample_pose(&self, normalized_time: F, parameters: &AnimationParameters<F>) -> AnimationPose<F> {
+        let time = if self.looping {
+            let time = normalized_time - normalized_time.floor();
+            // 恰好播放完整数次时取结尾
+            if time == F::zero() && normalized_time > F::zero() { F::one() } else { time }
+        } else {
+            normalized_time.clamp(F::zero(), F::one())
+        };
+        self.motion.sample_pose(time, parameters, &self.amountcalc)
+    }
+}
+
+/// 进行中的状态转换
+#[derive(Clone)]
+struct ActiveTransition<F: KeyFrameCurveScalar> {
+    /// 源状态
+    from: usize,
+    from_time: F,
+    elapsed: F,
+    duration: F,
+    amountcalc: AnimationAmountCalc<F>,
+}
+
+/// 由参数驱动的动画状态机
+pub struct AnimationStateMachine<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    states: Vec<AnimationState<F>>,
+    parameters: AnimationParameters<F>,
+    current: usize,
+    /// 当前状态的归一化时间, 循环状态累计计算
+    current_time: F,
+    transition: Option<ActiveTransition<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> Default for AnimationStateMachine<F> {
+    fn default() -> Self {
+        Self {
+            states: Vec::new(),
+            parameters: AnimationParameters::new(),
+            current: 0,
+            current_time: F::zero(),
+            transition: None,
+        }
+    }
+}
+
+impl<F: KeyFrameCurveScalar> AnimationStateMachine<F> {
+    /// 第一个添加的状态为初始状态
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 添加状态, 返回状态序号
+    pub fn add_state(&mut self, state: AnimationState<F>) -> usize {
+        self.states.push(state);
+        self.states.len() - 1
+    }
+    /// 为状态添加转换, 状态不存在时忽略
+    pub fn add_transition(&mut self, from: usize, transition: AnimationTransition<F>) {
+        if let Some(state) = self.states.get_mut(from) {
+            state.transitions.push(transition);
+        }
+    }
+    pub fn states(&self) -> &[AnimationState<F>] {
+        &self.states
+    }
+    pub fn state(&self, index: usize) -> Option<&AnimationState<F>> {
+        self.states.get(index)
+    }
+    pub fn state_mut(&mut self, index: usize) -> Option<&mut AnimationState<F>> {
+        self.states.get_mut(index)
+    }
+    pub fn find_state(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+    pub fn parameters(&self) -> &AnimationParameters<F> {
+        &self.parameters
+    }
+    pub fn parameters_mut(&mut self) -> &mut AnimationParameters<F> {
+        &mut self.parameters
+    }
+    pub fn current_state(&self) -> usize {
+        self.current
+    }
+    /// 当前状态的归一化时间, 循环状态累计计算
+    pub fn normalized_time(&self) -> F {
+        self.current_time
+    }
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// 立即切换到指定状态, 从头播放
+    pub fn play(&mut self, state: usize) {
+        if state < self.states.len() {
+            self.current = state;
+            self.current_time = F::zero();
+            self.transition = None;
+        }
+    }
+
+    /// 淡入淡出到指定状态, 从头播放
+    pub fn cross_fade(&mut self, state: usize, duration: F, easing: EEasingMode) {
+        if state >= self.states.len() {
+            return;
+        }
+        if duration <= F::zero() || self.states.is_empty() {
+            self.play(state);
+            return;
+        }
+        self.transition = Some(ActiveTransition {
+            from: self.current,
+            from_time: self.current_time,
+            elapsed: F::zero(),
+            duration,
+            amountcalc: AnimationAmountCalc::from_easing(easing),
+        });
+        self.current = state;
+        self.current_time = F::zero();
+    }
+
+    /// 推进时间并检查转换
+    /// 转换进行中不检查新的转换
+    pub fn update(&mut self, delta: F) {
+        if self.states.is_empty() {
+            return;
+        }
+        self.current_time = self.advance(self.current, self.current_time, delta);
+        if let Some(mut transition) = self.transition.take() {
+            transition.from_time = self.advance(transition.from, transition.from_time, delta);
+            transition.elapsed += delta;
+            self.transition = if transition.elapsed >= transition.duration { None } else { Some(transition) };
+        }
+
+        if self.transition.is_none() {
+            let state = &self.states[self.current];
+            let found = state.transitions.iter().find(|transition| transition.can_transit(self.current_time, &self.parameters)).cloned();
+            if let Some(transition) = found {
+                for condition in &transition.conditions {
+                    if let EAnimationCondition::Trigger(name) = condition {
+                        self.parameters.reset_trigger(name);
+                    }
+                }
+                self.cross_fade(transition.to, transition.duration, transition.easing);
+            }
+        }
+    }
+
+    /// 计算当前姿态, 转换进行中时混合源状态与目标状态
+    pub fn evaluate(&self) -> AnimationPose<F> {
+        let state = match self.states.get(self.current) {
+            Some(state) => state,
+            None => return AnimationPose::new(),
+        };
+        let pose = state.sample_pose(self.current_time, &self.parameters);
+        match &self.transition {
+            Some(transition) => {
+                let mut from = self.states[transition.from].sample_pose(transition.from_time, &self.parameters);
+                let amount = transition.amountcalc.calc((transition.elapsed / transition.duration).clamp(F::zero(), F::one()));
+                from.blend(&pose, amount);
+                from
+            },
+            None => pose,
+        }
+    }
+
+    /// 推进状态的归一化时间
+    fn advance(&self, state: usize, time: F, delta: F) -> F {
+        let state = &self.states[state];
+        let duration = state.motion.duration(&self.parameters);
+        let time = if duration <= F::zero() { F::one() } else { time + delta * state.speed / duration };
+        if state.looping { time.max(F::zero()) } else { time.clamp(F::zero(), F::one()) }
+    }
+}
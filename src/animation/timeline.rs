@@ -0,0 +1,244 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{amount::AnimationAmountCalc, curve::{frame::{FrameDataValue, FrameValueSlerp, KeyFrameCurveScalar, KeyFrameCurveValue, KeyFrameDataType}, frame_curve::FrameCurve}};
+
+use super::{clip::{AnimationClipCurve, AnimationClipSink, ClipCurve}, pose::{AnimationPose, AnimationValue, MixValue}, tween::Tween};
+
+/// 擦除类型的补间
+trait TimelineTween<F: KeyFrameCurveScalar> {
+    /// 指定时间 (包含延迟) 的值
+    fn sample_value(&self, time: F) -> Box<dyn AnimationValue<F>>;
+}
+
+impl<T: FrameDataValue<F> + Send + Sync + 'static, F: KeyFrameCurveScalar> TimelineTween<F> for Tween<T, F> {
+    fn sample_value(&self, time: F) -> Box<dyn AnimationValue<F>> {
+        Box::new(MixValue { value: self.value_at(time), mix: T::interpolate })
+    }
+}
+
+enum ETimelineItem<F: KeyFrameCurveScalar> {
+    Curve {
+        target: String,
+        property: String,
+        data_type: KeyFrameDataType,
+        curve: Box<dyn AnimationClipCurve<F>>,
+        amountcalc: AnimationAmountCalc<F>,
+        duration: F,
+    },
+    Tween {
+        target: String,
+        property: String,
+        data_type: KeyFrameDataType,
+        tween: Box<dyn TimelineTween<F>>,
+        duration: F,
+    },
+    Timeline(Timeline<F>),
+}
+
+/// 时间轴中的一项 - 绑定到 目标路径 + 属性 的曲线或补间, 或嵌套的时间轴
+pub struct TimelineItem<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    item: ETimelineItem<F>,
+}
+
+impl<F: KeyFrameCurveScalar> TimelineItem<F> {
+    /// 曲线项, 时长为曲线结束帧对应的时间
+    pub fn curve<T: FrameDataValue<F> + Send + Sync + 'static>(target: &str, property: &str, data_type: KeyFrameDataType, curve: FrameCurve<T, F>) -> Self {
+        Self::curve_with_mix(target, property, data_type, curve, T::interpolate)
+    }
+    /// 旋转类曲线项, 与其他值混合时使用 `FrameValueSlerp::slerp`
+    pub fn rotation_curve<T: FrameDataValue<F> + FrameValueSlerp<F> + Send + Sync + 'static>(target: &str, property: &str, data_type: KeyFrameDataType, curve: FrameCurve<T, F>) -> Self {
+        Self::curve_with_mix(target, property, data_type, curve, T::slerp)
+    }
+    fn curve_with_mix<T: FrameDataValue<F> + Send + Sync + 'static>(target: &str, property: &str, data_type: KeyFrameDataType, curve: FrameCurve<T, F>, mix: fn(&T, &T, F) -> T) -> Self {
+        let duration = F::from_frame_index(curve.max_frame) / F::from_frame_index(curve.design_frame_per_second);
+        Self {
+            item: ETimelineItem::Curve {
+                target: String::from(target),
+                property: String::from(property),
+                data_type,
+                curve: Box::new(ClipCurve { curve, mix }),
+                amountcalc: AnimationAmountCalc::default(),
+                duration,
+            },
+        }
+    }
+    /// 补间项, 时长为补间包含延迟与重复的总时长, 无限重复时为单次播放时长 (包含延迟)
+    /// 补间按时间轴的时间采样, 自身的播放进度与完成回调不生效; 结束后保持结束值, 无限重复时持续重复
+    pub fn tween<T: FrameDataValue<F> + Send + Sync + 'static>(target: &str, property: &str, data_type: KeyFrameDataType, tween: Tween<T, F>) -> Self {
+        let duration = tween.total_duration().unwrap_or(tween.delay() + tween.duration());
+        Self {
+            item: ETimelineItem::Tween {
+                target: String::from(target),
+                property: String::from(property),
+                data_type,
+                tween: Box::new(tween),
+                duration,
+            },
+        }
+    }
+    /// 嵌套时间轴
+    pub fn timeline(timeline: Timeline<F>) -> Self {
+        Self { item: ETimelineItem::Timeline(timeline) }
+    }
+    /// 曲线项采样使用的进度计算, 对补间与嵌套时间轴无效
+    pub fn with_amountcalc(mut self, calc: AnimationAmountCalc<F>) -> Self {
+        if let ETimelineItem::Curve { amountcalc, .. } = &mut self.item {
+            *amountcalc = calc;
+        }
+        self
+    }
+    pub fn duration(&self) -> F {
+        match &self.item {
+            ETimelineItem::Curve { duration, .. } | ETimelineItem::Tween { duration, .. } => *duration,
+            ETimelineItem::Timeline(timeline) => timeline.duration(),
+        }
+    }
+}
+
+/// 展开嵌套后的一项, 按开始时间排序
+/// nested 为嵌套时间轴中展开项的序号, items[index] 不是嵌套时间轴时无效
+#[derive(Clone, Copy)]
+struct FlatItem<F: KeyFrameCurveScalar> {
+    start: F,
+    index: usize,
+    nested: usize,
+}
+
+/// 时间轴 - 按时间偏移编排曲线, 支持顺序/并行/交错编排与嵌套
+/// 时间单位为秒
+pub struct Timeline<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    /// (开始时间, 项)
+    items: Vec<(F, TimelineItem<F>)>,
+    /// 展开嵌套后的所有曲线与补间项, 按开始时间稳定排序 (开始时间相同时先添加的在前)
+    flat: Vec<FlatItem<F>>,
+    /// 最后添加项的开始时间, 供 `with` 使用
+    last_start: F,
+}
+
+impl<F: KeyFrameCurveScalar> Default for Timeline<F> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            flat: Vec::new(),
+            last_start: F::zero(),
+        }
+    }
+}
+
+impl<F: KeyFrameCurveScalar> Timeline<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// 顺序编排 - 每项在上一项结束后开始
+    pub fn sequence<I: IntoIterator<Item = TimelineItem<F>>>(items: I) -> Self {
+        let mut timeline = Self::new();
+        for item in items {
+            timeline.then(item);
+        }
+        timeline
+    }
+    /// 并行编排 - 所有项同时开始
+    pub fn parallel<I: IntoIterator<Item = TimelineItem<F>>>(items: I) -> Self {
+        let mut timeline = Self::new();
+        for item in items {
+            timeline.insert(F::zero(), item);
+        }
+        timeline
+    }
+    /// 交错编排 - 第 i 项在 i * interval 开始
+    pub fn stagger<I: IntoIterator<Item = TimelineItem<F>>>(items: I, interval: F) -> Self {
+        let mut timeline = Self::new();
+        let mut start = F::zero();
+        for item in items {
+            timeline.insert(start, item);
+            start += interval;
+        }
+        timeline
+    }
+
+    /// 在指定时间插入
+    pub fn insert(&mut self, start: F, item: TimelineItem<F>) -> &mut Self {
+        self.last_start = start;
+        let index = self.items.len();
+        match &item.item {
+            ETimelineItem::Timeline(timeline) => {
+                for (nested, flat) in timeline.flat.iter().enumerate() {
+                    self.insert_flat(FlatItem { start: start + flat.start, index, nested });
+                }
+            },
+            _ => self.insert_flat(FlatItem { start, index, nested: 0 }),
+        }
+        self.items.push((start, item));
+        self
+    }
+    /// 在当前所有项结束后插入
+    pub fn then(&mut self, item: TimelineItem<F>) -> &mut Self {
+        let start = self.duration();
+        self.insert(start, item)
+    }
+    /// 与最后添加的项同时开始
+    pub fn with(&mut self, item: TimelineItem<F>) -> &mut Self {
+        let start = self.last_start;
+        self.insert(start, item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// 总时长 - 所有项结束时间的最大值
+    pub fn duration(&self) -> F {
+        self.items.iter().fold(F::zero(), |duration, (start, item)| duration.max(*start + item.duration()))
+    }
+
+    /// 计算指定时间 (秒) 各轨道 (目标路径 + 属性) 的值
+    ///
+    /// * 同一轨道取已开始的项中开始最晚的一项, 该项结束后保持结束值
+    /// * 轨道的第一项开始前取该项的起始值
+    /// * 各项在插入时已按开始时间排序, 计算时不再排序, 每条轨道只采样一次
+    ///
+    pub fn evaluate(&self, time: F) -> AnimationPose<F> {
+        let started = self.flat.partition_point(|item| item.start <= time);
+        let mut pose = AnimationPose::new();
+        // 已开始的项从后往前, 未开始的项从前往后, 每条轨道取遇到的第一项
+        for flat in self.flat[..started].iter().rev().chain(self.flat[started..].iter()) {
+            let (item, start) = self.flat_item(flat, F::zero());
+            let local = (time - start).max(F::zero());
+            match &item.item {
+                ETimelineItem::Curve { target, property, data_type, curve, amountcalc, duration } => {
+                    if !pose.contains(target, property) {
+                        pose.insert(target, property, *data_type, curve.sample_value(local.min(*duration), amountcalc));
+                    }
+                },
+                ETimelineItem::Tween { target, property, data_type, tween, .. } => {
+                    if !pose.contains(target, property) {
+                        pose.insert(target, property, *data_type, tween.sample_value(local));
+                    }
+                },
+                ETimelineItem::Timeline(_) => {},
+            }
+        }
+        pose
+    }
+
+    /// 计算指定时间 (秒) 各轨道的值, 依次交给 `sink`
+    pub fn sample<S: AnimationClipSink + ?Sized>(&self, time: F, sink: &mut S) {
+        self.evaluate(time).apply(sink);
+    }
+
+    fn insert_flat(&mut self, flat: FlatItem<F>) {
+        let position = self.flat.partition_point(|item| item.start <= flat.start);
+        self.flat.insert(position, flat);
+    }
+
+    /// 展开项对应的曲线或补间项, 及其在最外层时间轴中的开始时间
+    fn flat_item(&self, flat: &FlatItem<F>, offset: F) -> (&TimelineItem<F>, F) {
+        let (start, item) = &self.items[flat.index];
+        match &item.item {
+            ETimelineItem::Timeline(timeline) => timeline.flat_item(&timeline.flat[flat.nested], offset + *start),
+            _ => (item, offset + *start),
+        }
+    }
+}
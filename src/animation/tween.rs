@@ -0,0 +1,159 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{amount::AnimationAmountCalc, curve::frame::{FrameDataValue, KeyFrameCurveScalar, KeyFrameCurveValue}};
+
+use super::player::{AnimationPlayer, EFillMode, EIterationCount, EPlaybackDirection};
+
+/// 计算速度的时间差与时长之比
+const VELOCITY_EPSILON: f64 = 0.001;
+
+/// 完成回调, 参数为结束值
+type TweenCallback<T> = Box<dyn FnMut(&T)>;
+
+/// 补间动画 - from -> to 的一次性动画, 时间单位为秒
+pub struct Tween<T: FrameDataValue<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    from: T,
+    to: T,
+    amountcalc: AnimationAmountCalc<F>,
+    player: AnimationPlayer<F>,
+    /// 重定向时的速度修正 (旧速度 - 新速度) * 时间差, 仅作用于第一次播放
+    correction: Option<(T, F)>,
+    completed: bool,
+    on_complete: Vec<TweenCallback<T>>,
+}
+
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> Tween<T, F> {
+    pub fn new(from: T, to: T, duration: F) -> Self {
+        let mut player = AnimationPlayer::new(duration);
+        player.fill = EFillMode::Both;
+        Self {
+            from,
+            to,
+            amountcalc: AnimationAmountCalc::default(),
+            player,
+            correction: None,
+            completed: false,
+            on_complete: Vec::new(),
+        }
+    }
+    pub fn with_amountcalc(mut self, amountcalc: AnimationAmountCalc<F>) -> Self {
+        self.amountcalc = amountcalc;
+        self
+    }
+    /// 开始前的延迟 (秒)
+    pub fn with_delay(mut self, delay: F) -> Self {
+        self.player.delay = delay;
+        self
+    }
+    /// 播放完后再重复的次数
+    pub fn with_repeat(mut self, repeat: u32) -> Self {
+        self.player.iterations = EIterationCount::Count(F::from_f64_value(repeat as f64) + F::one());
+        self
+    }
+    /// 无限重复
+    pub fn with_infinite_repeat(mut self) -> Self {
+        self.player.iterations = EIterationCount::Infinite;
+        self
+    }
+    /// 重复时往返播放
+    pub fn with_yoyo(mut self, yoyo: bool) -> Self {
+        self.player.direction = if yoyo { EPlaybackDirection::Alternate } else { EPlaybackDirection::Normal };
+        self
+    }
+    /// 添加完成回调, 参数为结束值
+    pub fn on_complete<C: FnMut(&T) + 'static>(mut self, callback: C) -> Self {
+        self.on_complete.push(Box::new(callback));
+        self
+    }
+
+    pub fn from(&self) -> &T {
+        &self.from
+    }
+    pub fn to(&self) -> &T {
+        &self.to
+    }
+    /// 单次播放时长
+    pub fn duration(&self) -> F {
+        self.player.duration()
+    }
+    pub fn delay(&self) -> F {
+        self.player.delay
+    }
+    /// 包含延迟与重复的总时长, 无限重复返回 None
+    pub fn total_duration(&self) -> Option<F> {
+        self.player.end_time()
+    }
+    /// 已播放时间 (包含延迟)
+    pub fn elapsed(&self) -> F {
+        self.player.current_time()
+    }
+    pub fn is_complete(&self) -> bool {
+        self.completed
+    }
+
+    /// 当前值
+    pub fn value(&self) -> T {
+        self.value_at(self.player.current_time())
+    }
+
+    /// 推进时间并返回当前值, 播放完成时调用完成回调
+    pub fn tick(&mut self, delta: F) -> T {
+        self.player.tick(delta);
+        let value = self.value();
+        if !self.completed && self.player.is_finished() {
+            self.completed = true;
+            for callback in self.on_complete.iter_mut() {
+                callback(&value);
+            }
+        }
+        value
+    }
+
+    /// 从头播放
+    pub fn restart(&mut self) {
+        self.player.seek(F::zero());
+        self.completed = false;
+    }
+
+    /// 播放途中改变目标, 从当前值出发, 保持当前速度平滑过渡到新目标
+    /// 延迟与重复设置保留, 延迟不再生效
+    pub fn retarget(&mut self, to: T, duration: F) {
+        let now = self.player.current_time();
+        let epsilon = F::from_f64_value(VELOCITY_EPSILON) * duration;
+        let current = self.value_at(now);
+        // 旧速度 * 时间差
+        let old_delta = self.value_at(now + epsilon).append(&current, -F::one());
+
+        self.from = current;
+        self.to = to;
+        self.correction = None;
+        self.player.delay = F::zero();
+        self.player.set_duration(duration);
+        self.player.seek(F::zero());
+        self.completed = false;
+
+        if epsilon > F::zero() {
+            // 新插值的初速度 * 时间差
+            let new_delta = self.value_at(epsilon).append(&self.from, -F::one());
+            self.correction = Some((old_delta.append(&new_delta, -F::one()), epsilon));
+        }
+    }
+
+    /// 指定时间 (包含延迟) 的值
+    pub(crate) fn value_at(&self, time: F) -> T {
+        let mut player = self.player;
+        player.seek(time);
+        let progress = player.progress().unwrap_or(F::zero());
+        let value = self.from.interpolate(&self.to, self.amountcalc.calc(progress));
+
+        match (&self.correction, player.current_iteration()) {
+            (Some((correction, epsilon)), Some(iteration)) if iteration == F::zero() && player.duration() > F::zero() => {
+                // Hermite 基函数 h10(s) = s³ - 2s² + s: 起点斜率为 1, 终点值与斜率为 0
+                let s = progress;
+                let h10 = s * s * s - (F::one() + F::one()) * s * s + s;
+                value.append(correction, h10 * player.duration() / *epsilon)
+            },
+            _ => value,
+        }
+    }
+}
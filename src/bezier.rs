@@ -1,8 +1,18 @@
 //!  实现 bezier 曲线相关函数
 
-use nalgebra::RealField;
+use crate::float::CurveFloat;
 
-pub fn cubic_bezier<T: RealField + Copy>(_x1: T, _y1: T, _x2: T, _y2: T, t: T) -> T {
+pub fn cubic_bezier<T: CurveFloat>(_x1: T, _y1: T, _x2: T, _y2: T, t: T) -> T {
+    let one = T::one();
+    let three = T::from_f32(3.0).unwrap();
+
+    let refined_t = cubic_bezier_solve(_x1, _x2, t);
+
+    three * T::powi(one - refined_t, 2) * refined_t * _y1 + three * (one - refined_t) * T::powi(refined_t, 2) * _y2 + T::powi(refined_t, 3)
+}
+
+/// 求 x(s) = t 的曲线参数 s, 控制点为 (0, 0), (x1, _), (x2, _), (1, 1)
+pub fn cubic_bezier_solve<T: CurveFloat>(_x1: T, _x2: T, t: T) -> T {
     let _0  = T::from_f32(0.0).unwrap();
     let _1  = T::from_f32(1.0).unwrap();
     let _2  = T::from_f32(2.0).unwrap();
@@ -26,7 +36,7 @@ pub fn cubic_bezier<T: RealField + Copy>(_x1: T, _y1: T, _x2: T, _y2: T, t: T) -
         refined_t = T::min(_1, T::max(_0, refined_t));
     };
 
-    _3 * T::powi(_1 - refined_t, 2) * refined_t * _y1 + _3 * (_1 - refined_t) * T::powi(refined_t, 2) * _y2 + T::powi(refined_t, 3)
+    refined_t
 }
 
 #[test]
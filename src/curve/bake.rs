@@ -0,0 +1,114 @@
+//!
+//! 曲线烘焙 - 按目标帧率均匀采样任意曲线, 生成关键帧数值曲线
+
+use crate::amount::AnimationAmountCalc;
+
+use super::{frame::{FrameDataValue, KeyFrameCurveScalar}, frame_curve::FrameCurve, ErrorCurve, FrameIndex, FramePerSecond};
+
+/// 误差统计时每两个烘焙帧之间的采样数
+const BAKE_ERROR_SUBSAMPLES: usize = 4;
+/// 帧率换算时视为整数帧的误差
+const FRAME_TOLERANCE: f64 = 0.0001;
+
+/// 烘焙结果的曲线类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EBakeMode {
+    /// 关键帧之间线性插值
+    #[default]
+    FrameValues,
+    /// 关键帧之间阶跃
+    FrameValuesStep,
+}
+
+/// 烘焙曲线与源曲线的误差
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BakeError<F: KeyFrameCurveScalar> {
+    /// 最大误差
+    pub max: F,
+    /// 平均误差
+    pub mean: F,
+    /// 最大误差出现的时间 (秒)
+    pub max_time: F,
+}
+
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> FrameCurve<T, F> {
+    /// 烘焙 - 以目标帧率均匀采样, 生成 FrameValues 或 FrameValuesStep 曲线
+    ///
+    /// * [frame_per_second] - 目标帧率
+    /// * [mode] - 烘焙结果的曲线类型
+    /// * [amountcalc] - 采样源曲线时的进度计算, 其效果会烘焙进结果, 结果应以默认进度计算采样
+    ///
+    /// 源曲线没有关键帧时返回空曲线; 结束时间 * 目标帧率超出 FrameIndex 范围时返回 `ErrorCurve::FrameIndexOverflow`
+    pub fn bake(&self, frame_per_second: FramePerSecond, mode: EBakeMode, amountcalc: &AnimationAmountCalc<F>) -> Result<FrameCurve<T, F>, ErrorCurve> {
+        let mut result = match mode {
+            EBakeMode::FrameValues => FrameCurve::curve_frame_values(frame_per_second),
+            EBakeMode::FrameValuesStep => FrameCurve::curve_frame_values_step(frame_per_second),
+        };
+        let (start, end) = match self.time_range() {
+            Some(range) => range,
+            None => return Ok(result),
+        };
+
+        let fps = F::from_frame_index(frame_per_second);
+        let (first, last) = bake_frame_range(start, end, fps)?;
+        for frame in first..=last {
+            // 超出源曲线范围的帧取两端的值
+            let time = (F::from_frame_index(frame) / fps).clamp(start, end);
+            result.curve_frame_values_frame(frame, self.interple(time, amountcalc));
+        }
+        Ok(result)
+    }
+
+    /// 烘焙并统计与源曲线的误差
+    ///
+    /// * [distance] - 两个值之间的距离, 如 `|a, b| (a - b).abs()`
+    ///
+    /// 在每两个烘焙帧之间取若干点比较, 包含关键帧本身
+    pub fn bake_with_error<D: Fn(&T, &T) -> F>(&self, frame_per_second: FramePerSecond, mode: EBakeMode, amountcalc: &AnimationAmountCalc<F>, distance: D) -> Result<(FrameCurve<T, F>, BakeError<F>), ErrorCurve> {
+        let result = self.bake(frame_per_second, mode, amountcalc)?;
+        let mut error = BakeError { max: F::zero(), mean: F::zero(), max_time: F::zero() };
+        let (start, end) = match self.time_range() {
+            Some(range) => range,
+            None => return Ok((result, error)),
+        };
+
+        let fps = F::from_frame_index(frame_per_second);
+        let (first, last) = bake_frame_range(start, end, fps)?;
+        let baked_amountcalc = AnimationAmountCalc::default();
+        let samples = (last - first) as usize * BAKE_ERROR_SUBSAMPLES;
+        let mut total = F::zero();
+        for index in 0..=samples {
+            let frame = F::from_frame_index(first) + F::from_f64_value(index as f64 / BAKE_ERROR_SUBSAMPLES as f64);
+            let time = (frame / fps).clamp(start, end);
+            let value = distance(&self.interple(time, amountcalc), &result.interple(time, &baked_amountcalc));
+            if value > error.max {
+                error.max = value;
+                error.max_time = time;
+            }
+            total += value;
+        }
+        error.mean = total / F::from_f64_value((samples + 1) as f64);
+        Ok((result, error))
+    }
+
+    /// 源曲线的时间范围 (秒), 没有关键帧时为 None
+    fn time_range(&self) -> Option<(F, F)> {
+        if self.min_frame > self.max_frame {
+            return None;
+        }
+        let fps = F::from_frame_index(self.design_frame_per_second);
+        Some((F::from_frame_index(self.min_frame) / fps, F::from_frame_index(self.max_frame) / fps))
+    }
+}
+
+/// 覆盖时间范围的烘焙帧序号 [first, last], 容忍帧率换算的舍入误差
+/// last 超出 FrameIndex 范围时返回错误, 而非截断
+fn bake_frame_range<F: KeyFrameCurveScalar>(start: F, end: F, fps: F) -> Result<(FrameIndex, FrameIndex), ErrorCurve> {
+    let tolerance = F::from_f64_value(FRAME_TOLERANCE);
+    let first = (start * fps + tolerance).floor();
+    let last = -(tolerance - end * fps).floor();
+    if last.max(first) > F::from_frame_index(FrameIndex::MAX) {
+        return Err(ErrorCurve::FrameIndexOverflow);
+    }
+    Ok((first.to_frame_index(), last.max(first).to_frame_index()))
+}
@@ -0,0 +1,177 @@
+//!
+//! 曲线组 - 多个通道共享关键帧时间, 数值按关键帧连续存放 (结构数组)
+//! 一次查找所在区间, 再对所有通道批量插值
+
+use alloc::vec::Vec;
+
+use super::{curves::EFrameCurveType, frame::{FrameDataValue, KeyFrameCurveScalar, KeyFrameCurveValue}, frame_curve::FrameCurve, ErrorCurve, FrameIndex, FramePerSecond};
+
+/// 曲线组的插值方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ECurveBankMode {
+    /// 线性插值 - 同 FrameValues
+    Linear,
+    /// 阶跃 - 同 FrameValuesStep
+    Step,
+    /// 三次样条 - 同 GLTFCubicSpline
+    CubicSpline,
+}
+
+/// 共享关键帧时间的曲线组
+pub struct CurveBank<T: FrameDataValue<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub design_frame_per_second: FramePerSecond,
+    mode: ECurveBankMode,
+    channels: usize,
+    frames: Vec<FrameIndex>,
+    /// [关键帧][通道]
+    values: Vec<T>,
+    /// CubicSpline 的 入切线 / 出切线, 布局同 values
+    intangents: Vec<T>,
+    outtangents: Vec<T>,
+    marker: core::marker::PhantomData<F>,
+}
+
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> CurveBank<T, F> {
+    pub fn new(design_frame_per_second: FramePerSecond, mode: ECurveBankMode, channels: usize) -> Self {
+        Self {
+            design_frame_per_second,
+            mode,
+            channels,
+            frames: Vec::new(),
+            values: Vec::new(),
+            intangents: Vec::new(),
+            outtangents: Vec::new(),
+            marker: core::marker::PhantomData,
+        }
+    }
+
+    /// 由关键帧时间相同的多条曲线构建, 曲线类型需为 FrameValues, FrameValuesStep 或 GLTFCubicSpline 且一致
+    pub fn from_curves(curves: &[&FrameCurve<T, F>]) -> Result<Self, ErrorCurve> {
+        let first = match curves.first() {
+            Some(first) => first,
+            None => return Err(ErrorCurve::KeyFramesMismatch),
+        };
+        let mode = match first.curve_type() {
+            EFrameCurveType::FrameValues => ECurveBankMode::Linear,
+            EFrameCurveType::FrameValuesStep => ECurveBankMode::Step,
+            EFrameCurveType::GLTFCubicSpline => ECurveBankMode::CubicSpline,
+            _ => return Err(ErrorCurve::KeyFramesMismatch),
+        };
+        for curve in curves.iter() {
+            if curve.design_frame_per_second != first.design_frame_per_second {
+                return Err(ErrorCurve::FramePerSecondMismatch);
+            }
+            let keys = match mode {
+                ECurveBankMode::CubicSpline => curve.cubic_spline_values.len(),
+                _ => curve.values.len(),
+            };
+            if curve.curve_type() != first.curve_type() || curve.frames != first.frames || keys != first.frames.len() {
+                return Err(ErrorCurve::KeyFramesMismatch);
+            }
+        }
+
+        let mut bank = Self::new(first.design_frame_per_second, mode, curves.len());
+        let keys = first.frames.len();
+        bank.frames = first.frames.clone();
+        for key in 0..keys {
+            for curve in curves.iter() {
+                match mode {
+                    ECurveBankMode::CubicSpline => {
+                        let keyframe = &curve.cubic_spline_values[key];
+                        bank.values.push(keyframe.value().clone());
+                        bank.intangents.push(keyframe.intangent().clone());
+                        bank.outtangents.push(keyframe.outtangent().clone());
+                    },
+                    _ => bank.values.push(curve.values[key].clone()),
+                }
+            }
+        }
+        Ok(bank)
+    }
+
+    pub fn mode(&self) -> ECurveBankMode {
+        self.mode
+    }
+    /// 通道数量
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+    pub fn frames(&self) -> &[FrameIndex] {
+        &self.frames
+    }
+    /// 关键帧各通道的值
+    pub fn values(&self, key: usize) -> &[T] {
+        &self.values[key * self.channels..(key + 1) * self.channels]
+    }
+
+    /// 添加关键帧, values 按通道顺序; CubicSpline 模式切线取 0
+    pub fn add_key(&mut self, frame: FrameIndex, values: &[T]) -> Result<(), ErrorCurve> {
+        if values.len() != self.channels {
+            return Err(ErrorCurve::ChannelCountMismatch);
+        }
+        let zeros: Vec<T> = values.iter().map(|value| value.append(value, -F::one())).collect();
+        self.insert_key(frame, values, &zeros, &zeros);
+        Ok(())
+    }
+
+    /// 添加 CubicSpline 关键帧, 切线单位为每秒
+    pub fn add_cubic_spline_key(&mut self, frame: FrameIndex, values: &[T], intangents: &[T], outtangents: &[T]) -> Result<(), ErrorCurve> {
+        if values.len() != self.channels || intangents.len() != self.channels || outtangents.len() != self.channels {
+            return Err(ErrorCurve::ChannelCountMismatch);
+        }
+        self.insert_key(frame, values, intangents, outtangents);
+        Ok(())
+    }
+
+    /// 在指定时间 (秒) 计算所有通道, 结果按通道顺序写入 result
+    /// result 长度须与通道数量一致; 曲线组没有关键帧时返回错误, result 保持不变
+    pub fn evaluate_all(&self, time: F, result: &mut [T]) -> Result<(), ErrorCurve> {
+        if result.len() != self.channels {
+            return Err(ErrorCurve::ChannelCountMismatch);
+        }
+        if self.frames.is_empty() {
+            return Err(ErrorCurve::KeyFramesEmpty);
+        }
+        let target_frame = time * F::from_frame_index(self.design_frame_per_second);
+        let next = self.frames.partition_point(|frame| F::from_frame_index(*frame) <= target_frame);
+        if next == 0 || next == self.frames.len() {
+            result.clone_from_slice(self.values(next.saturating_sub(1)));
+            return Ok(());
+        }
+
+        let pre = next - 1;
+        let (frame1, frame2) = (F::from_frame_index(self.frames[pre]), F::from_frame_index(self.frames[next]));
+        let amount = (target_frame - frame1) / (frame2 - frame1);
+        let (values1, values2) = (self.values(pre), self.values(next));
+        match self.mode {
+            ECurveBankMode::Linear => {
+                for (result, (value1, value2)) in result.iter_mut().zip(values1.iter().zip(values2.iter())) {
+                    *result = value1.interpolate(value2, amount);
+                }
+            },
+            ECurveBankMode::Step => {
+                result.clone_from_slice(if amount < F::from_f64_value(0.5) { values1 } else { values2 });
+            },
+            ECurveBankMode::CubicSpline => {
+                let frame_delta = (frame2 - frame1) / F::from_frame_index(self.design_frame_per_second);
+                let tangents1 = &self.outtangents[pre * self.channels..next * self.channels];
+                let tangents2 = &self.intangents[next * self.channels..(next + 1) * self.channels];
+                for (index, result) in result.iter_mut().enumerate() {
+                    *result = T::hermite(&values1[index], &tangents1[index], &values2[index], &tangents2[index], amount, frame_delta);
+                }
+            },
+        }
+        Ok(())
+    }
+
+    fn insert_key(&mut self, frame: FrameIndex, values: &[T], intangents: &[T], outtangents: &[T]) {
+        let key = self.frames.partition_point(|value| *value <= frame);
+        self.frames.insert(key, frame);
+        let offset = key * self.channels;
+        self.values.splice(offset..offset, values.iter().cloned());
+        if self.mode == ECurveBankMode::CubicSpline {
+            self.intangents.splice(offset..offset, intangents.iter().cloned());
+            self.outtangents.splice(offset..offset, outtangents.iter().cloned());
+        }
+    }
+}
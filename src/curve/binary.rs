@@ -0,0 +1,375 @@
+//!
+//! 曲线二进制格式 - 小端序, 带版本号
+//!
+//! 曲线记录布局 (各段起始按 8 字节对齐, 缓冲区 8 字节对齐时 帧序号 / 数值 数组可直接映射):
+//!
+//! | 字节 | 内容 |
+//! | --- | --- |
+//! | 16 | 头: "PICV", 版本 u16, 曲线类型 u8, 标量字节数 u8, 数值字节数 u32, 帧率 u16, 标记 u8, 缓动模式 u8 |
+//! | 4 * 标量 | CubicBezier 参数 |
+//! | 8 | 起始帧 u16, 结束帧 u16, 帧数 u16, 保留 u16 |
+//! | 16 | 帧序号 / 数值 / MinMaxCurve 关键帧 / GLTFCubicSpline 关键帧 数量, 各 u32 |
+//! | - | 起始值, 变化域值 (标记存在时) |
+//! | - | 帧序号, 数值, MinMaxCurve 关键帧 (值, 入切线, 出切线), GLTFCubicSpline 关键帧 (值, 入切线, 出切线) |
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{Quaternion, SVector, Scalar};
+
+use crate::{amount::AnimationAmountCalc, easing::EEasingMode, steps::EStepMode, EAmountMode};
+
+use super::{curves::EFrameCurveType, frame::{CurveFrameValue, FrameDataValue, KeyFrameCurveScalar}, frame_curve::FrameCurve, ErrorCurve, FrameIndex};
+
+/// 曲线记录标识
+const CURVE_MAGIC: [u8; 4] = *b"PICV";
+/// 进度计算记录标识
+const AMOUNT_MAGIC: [u8; 4] = *b"PIAC";
+/// 当前格式版本
+pub const BINARY_VERSION: u16 = 1;
+/// 段对齐字节数
+const ALIGN: usize = 8;
+
+const FLAG_VALUE_OFFSET: u8 = 0b01;
+const FLAG_VALUE_SCALAR: u8 = 0b10;
+
+const CURVE_TYPES: [EFrameCurveType; 6] = [
+    EFrameCurveType::FrameValues,
+    EFrameCurveType::FrameValuesStep,
+    EFrameCurveType::EasingCurve,
+    EFrameCurveType::MinMaxCurve,
+    EFrameCurveType::CubicBezierCurve,
+    EFrameCurveType::GLTFCubicSpline,
+];
+
+const EASING_MODES: [EEasingMode; 31] = [
+    EEasingMode::None,
+    EEasingMode::BackIn, EEasingMode::BackOut, EEasingMode::BackInOut,
+    EEasingMode::CircleIn, EEasingMode::CircleOut, EEasingMode::CircleInOut,
+    EEasingMode::CubicIn, EEasingMode::CubicOut, EEasingMode::CubicInOut,
+    EEasingMode::SineIn, EEasingMode::SineOut, EEasingMode::SineInOut,
+    EEasingMode::QuadIn, EEasingMode::QuadOut, EEasingMode::QuadInOut,
+    EEasingMode::QuartIn, EEasingMode::QuartOut, EEasingMode::QuartInOut,
+    EEasingMode::QuintIn, EEasingMode::QuintOut, EEasingMode::QuintInOut,
+    EEasingMode::ExpoIn, EEasingMode::ExpoOut, EEasingMode::ExpoInOut,
+    EEasingMode::ElasticIn, EEasingMode::ElasticOut, EEasingMode::ElasticInOut,
+    EEasingMode::BounceIn, EEasingMode::BounceOut, EEasingMode::BounceInOut,
+];
+
+const STEP_MODES: [EStepMode; 4] = [EStepMode::JumpStart, EStepMode::JumpEnd, EStepMode::JumpNone, EStepMode::JumpBoth];
+
+/// 可按固定字节数读写的数值 (小端序)
+pub trait FrameValueBytes: Sized {
+    /// 字节数
+    fn byte_size() -> usize;
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+    /// bytes 长度为 byte_size()
+    fn read_bytes(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_frame_value_bytes_primitive {
+    ($($ty:ty),*) => {
+        $(
+            impl FrameValueBytes for $ty {
+                fn byte_size() -> usize {
+                    core::mem::size_of::<$ty>()
+                }
+                fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                    bytes.extend_from_slice(&self.to_le_bytes());
+                }
+                fn read_bytes(bytes: &[u8]) -> Self {
+                    let mut buffer = [0u8; core::mem::size_of::<$ty>()];
+                    buffer.copy_from_slice(bytes);
+                    <$ty>::from_le_bytes(buffer)
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_bytes_primitive!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+/// 定长数组
+impl<T: FrameValueBytes, const N: usize> FrameValueBytes for [T; N] {
+    fn byte_size() -> usize {
+        T::byte_size() * N
+    }
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.iter().for_each(|value| value.write_bytes(bytes));
+    }
+    fn read_bytes(bytes: &[u8]) -> Self {
+        core::array::from_fn(|index| T::read_bytes(&bytes[index * T::byte_size()..(index + 1) * T::byte_size()]))
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+/// Vector - 任意维度
+impl<N: Scalar + FrameValueBytes, const D: usize> FrameValueBytes for SVector<N, D> {
+    fn byte_size() -> usize {
+        N::byte_size() * D
+    }
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.iter().for_each(|value| value.write_bytes(bytes));
+    }
+    fn read_bytes(bytes: &[u8]) -> Self {
+        SVector::from_fn(|index, _| N::read_bytes(&bytes[index * N::byte_size()..(index + 1) * N::byte_size()]))
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+/// Quaternion - [x, y, z, w]
+impl<N: Scalar + FrameValueBytes> FrameValueBytes for Quaternion<N> {
+    fn byte_size() -> usize {
+        N::byte_size() * 4
+    }
+    fn write_bytes(&self, bytes: &mut Vec<u8>) {
+        self.coords.iter().for_each(|value| value.write_bytes(bytes));
+    }
+    fn read_bytes(bytes: &[u8]) -> Self {
+        Quaternion::from(SVector::<N, 4>::read_bytes(bytes))
+    }
+}
+
+impl<T: FrameDataValue<F> + FrameValueBytes, F: KeyFrameCurveScalar + FrameValueBytes> FrameCurve<T, F> {
+    /// 写入二进制记录
+    pub fn write_binary(&self, bytes: &mut Vec<u8>) {
+        let start = bytes.len();
+        let mut flags = 0;
+        if self.value_offset.is_some() {
+            flags |= FLAG_VALUE_OFFSET;
+        }
+        if self.value_scalar.is_some() {
+            flags |= FLAG_VALUE_SCALAR;
+        }
+        bytes.extend_from_slice(&CURVE_MAGIC);
+        bytes.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+        bytes.push(self.curve_type() as u8);
+        bytes.push(F::byte_size() as u8);
+        bytes.extend_from_slice(&(T::byte_size() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.design_frame_per_second.to_le_bytes());
+        bytes.push(flags);
+        bytes.push(self.easing_mode() as u8);
+
+        self.cubic_bezier_args().iter().for_each(|arg| arg.write_bytes(bytes));
+        pad(bytes, start);
+        for value in [self.min_frame, self.max_frame, self.frame_number, 0] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        for count in [self.frames.len(), self.values.len(), self.minmax_curve_values.len(), self.cubic_spline_values.len()] {
+            bytes.extend_from_slice(&(count as u32).to_le_bytes());
+        }
+        for value in self.value_offset.iter().chain(self.value_scalar.iter()) {
+            value.write_bytes(bytes);
+            pad(bytes, start);
+        }
+
+        self.frames.iter().for_each(|frame| bytes.extend_from_slice(&frame.to_le_bytes()));
+        pad(bytes, start);
+        self.values.iter().for_each(|value| value.write_bytes(bytes));
+        pad(bytes, start);
+        for keyframe in self.minmax_curve_values.iter() {
+            [*keyframe.value(), *keyframe.intangent(), *keyframe.outtangent()].iter().for_each(|value| value.write_bytes(bytes));
+        }
+        pad(bytes, start);
+        for keyframe in self.cubic_spline_values.iter() {
+            [keyframe.value(), keyframe.intangent(), keyframe.outtangent()].iter().for_each(|value| value.write_bytes(bytes));
+        }
+        pad(bytes, start);
+    }
+
+    pub fn to_binary(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_binary(&mut bytes);
+        bytes
+    }
+
+    /// 读取二进制记录, 返回曲线与记录的字节数
+    ///
+    /// 标识, 版本, 类型, 长度 或 数据不符时返回错误
+    pub fn read_binary(bytes: &[u8]) -> Result<(Self, usize), ErrorCurve> {
+        let mut reader = BinaryReader { bytes, offset: 0 };
+        if reader.take(4)? != CURVE_MAGIC {
+            return Err(ErrorCurve::BinaryInvalidHeader);
+        }
+        if reader.u16()? != BINARY_VERSION {
+            return Err(ErrorCurve::BinaryUnsupportedVersion);
+        }
+        let curve_type = *CURVE_TYPES.get(reader.u8()? as usize).ok_or(ErrorCurve::BinaryInvalidData)?;
+        if reader.u8()? as usize != F::byte_size() || reader.u32()? as usize != T::byte_size() {
+            return Err(ErrorCurve::BinaryValueTypeMismatch);
+        }
+        let design_frame_per_second = reader.u16()?;
+        let flags = reader.u8()?;
+        let easing_mode = reader.u8()?;
+        let easing_mode = *EASING_MODES.iter().find(|mode| **mode as u8 == easing_mode).ok_or(ErrorCurve::BinaryInvalidData)?;
+        if flags & !(FLAG_VALUE_OFFSET | FLAG_VALUE_SCALAR) != 0 {
+            return Err(ErrorCurve::BinaryInvalidData);
+        }
+
+        let args: [F; 4] = [reader.value()?, reader.value()?, reader.value()?, reader.value()?];
+        reader.align()?;
+        let (min_frame, max_frame, frame_number) = (reader.u16()?, reader.u16()?, reader.u16()?);
+        reader.u16()?;
+        let counts = [reader.u32()? as usize, reader.u32()? as usize, reader.u32()? as usize, reader.u32()? as usize];
+        let value_offset: Option<T> = if flags & FLAG_VALUE_OFFSET != 0 { let value = reader.value()?; reader.align()?; Some(value) } else { None };
+        let value_scalar: Option<T> = if flags & FLAG_VALUE_SCALAR != 0 { let value = reader.value()?; reader.align()?; Some(value) } else { None };
+
+        // 先校验总长度, 避免按损坏的数量分配内存
+        let size = |count: usize, size: usize| count.checked_mul(size).map(|bytes| bytes.div_ceil(ALIGN) * ALIGN);
+        let remain = [size(counts[0], 2), size(counts[1], T::byte_size()), size(counts[2], F::byte_size() * 3), size(counts[3], T::byte_size() * 3)]
+            .iter()
+            .try_fold(0usize, |total, size| size.and_then(|size| total.checked_add(size)))
+            .ok_or(ErrorCurve::BinaryInvalidData)?;
+        if reader.bytes.len() - reader.offset < remain {
+            return Err(ErrorCurve::BinaryUnexpectedEnd);
+        }
+
+        let frames: Vec<FrameIndex> = (0..counts[0]).map(|_| reader.u16()).collect::<Result<_, _>>()?;
+        reader.align()?;
+        let values: Vec<T> = (0..counts[1]).map(|_| reader.value()).collect::<Result<_, _>>()?;
+        reader.align()?;
+        let minmax_curve_values: Vec<CurveFrameValue<F>> = (0..counts[2]).map(|_| reader.keyframe()).collect::<Result<_, _>>()?;
+        reader.align()?;
+        let cubic_spline_values: Vec<CurveFrameValue<T>> = (0..counts[3]).map(|_| reader.keyframe()).collect::<Result<_, _>>()?;
+        reader.align()?;
+
+        // 数据与曲线类型一致
+        let keyframes = match curve_type {
+            EFrameCurveType::FrameValues | EFrameCurveType::FrameValuesStep => Some(values.len()),
+            EFrameCurveType::MinMaxCurve => Some(minmax_curve_values.len()),
+            EFrameCurveType::GLTFCubicSpline => Some(cubic_spline_values.len()),
+            EFrameCurveType::EasingCurve | EFrameCurveType::CubicBezierCurve => None,
+        };
+        let needs_range = matches!(curve_type, EFrameCurveType::EasingCurve | EFrameCurveType::CubicBezierCurve | EFrameCurveType::MinMaxCurve);
+        let valid = match keyframes {
+            Some(count) => count == frames.len()
+                && frames.windows(2).all(|pair| pair[0] <= pair[1])
+                && (frames.is_empty() || (min_frame == frames[0] && max_frame == frames[frames.len() - 1] && frame_number == max_frame - min_frame)),
+            None => frames.is_empty() && min_frame == 0 && max_frame == frame_number,
+        };
+        if !valid || (needs_range && (value_offset.is_none() || value_scalar.is_none())) {
+            return Err(ErrorCurve::BinaryInvalidData);
+        }
+
+        let mut curve = match curve_type {
+            EFrameCurveType::FrameValues => FrameCurve::curve_frame_values(design_frame_per_second),
+            EFrameCurveType::FrameValuesStep => FrameCurve::curve_frame_values_step(design_frame_per_second),
+            EFrameCurveType::GLTFCubicSpline => FrameCurve::curve_cubic_spline(design_frame_per_second),
+            EFrameCurveType::MinMaxCurve => FrameCurve::curve_minmax_curve(value_offset.clone().unwrap(), value_scalar.clone().unwrap(), design_frame_per_second),
+            EFrameCurveType::EasingCurve => FrameCurve::curve_easing(value_offset.clone().unwrap(), value_scalar.clone().unwrap(), frame_number, design_frame_per_second, easing_mode),
+            EFrameCurveType::CubicBezierCurve => FrameCurve::curve_cubic_bezier(value_offset.clone().unwrap(), value_scalar.clone().unwrap(), frame_number, design_frame_per_second, args[0], args[1], args[2], args[3]),
+        };
+        curve.value_offset = value_offset;
+        curve.value_scalar = value_scalar;
+        curve.frames = frames;
+        curve.values = values;
+        curve.minmax_curve_values = minmax_curve_values;
+        curve.cubic_spline_values = cubic_spline_values;
+        curve.min_frame = min_frame;
+        curve.max_frame = max_frame;
+        curve.frame_number = frame_number;
+        Ok((curve, reader.offset))
+    }
+
+    /// 读取二进制记录, 需恰好为一条记录
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, ErrorCurve> {
+        match Self::read_binary(bytes)? {
+            (curve, size) if size == bytes.len() => Ok(curve),
+            _ => Err(ErrorCurve::BinaryInvalidData),
+        }
+    }
+}
+
+impl<F: KeyFrameCurveScalar + FrameValueBytes> AnimationAmountCalc<F> {
+    /// 写入二进制记录: "PIAC", 版本 u16, 模式 u8, 子模式 u8, 标量字节数 u8, 保留 3 字节, 参数 4 * 标量
+    /// Lut 模式写入查找表的源进度计算, 读取后需重新建表
+    pub fn write_binary(&self, bytes: &mut Vec<u8>) {
+        if let Some(lut) = self.lut() {
+            lut.source().write_binary(bytes);
+            return;
+        }
+        let (mode, sub) = match self.mode() {
+            EAmountMode::None => (0, 0),
+            EAmountMode::Easing(mode) => (1, mode as u8),
+            EAmountMode::Steps(mode) => (2, mode as u8),
+            EAmountMode::CubicBezier => (3, 0),
+            EAmountMode::Lut(_) => (0, 0),
+        };
+        bytes.extend_from_slice(&AMOUNT_MAGIC);
+        bytes.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&[mode, sub, F::byte_size() as u8, 0, 0, 0]);
+        let param = self.param();
+        [param.0, param.1, param.2, param.3].iter().for_each(|value| value.write_bytes(bytes));
+    }
+
+    /// 读取二进制记录, 返回进度计算与记录的字节数
+    pub fn read_binary(bytes: &[u8]) -> Result<(Self, usize), ErrorCurve> {
+        let mut reader = BinaryReader { bytes, offset: 0 };
+        if reader.take(4)? != AMOUNT_MAGIC {
+            return Err(ErrorCurve::BinaryInvalidHeader);
+        }
+        if reader.u16()? != BINARY_VERSION {
+            return Err(ErrorCurve::BinaryUnsupportedVersion);
+        }
+        let (mode, sub) = (reader.u8()?, reader.u8()?);
+        if reader.u8()? as usize != F::byte_size() {
+            return Err(ErrorCurve::BinaryValueTypeMismatch);
+        }
+        reader.take(3)?;
+        let param: [F; 4] = [reader.value()?, reader.value()?, reader.value()?, reader.value()?];
+        let amountcalc = match (mode, sub) {
+            (0, 0) => AnimationAmountCalc::default(),
+            (1, sub) => AnimationAmountCalc::from_easing(*EASING_MODES.iter().find(|mode| **mode as u8 == sub).ok_or(ErrorCurve::BinaryInvalidData)?),
+            (2, sub) => {
+                let mode = *STEP_MODES.get(sub as usize).ok_or(ErrorCurve::BinaryInvalidData)?;
+                if !(param[0] >= F::one() && param[0] <= F::from_frame_index(FrameIndex::MAX)) {
+                    return Err(ErrorCurve::BinaryInvalidData);
+                }
+                AnimationAmountCalc::from_steps(param[0].to_frame_index(), mode)
+            },
+            (3, 0) => AnimationAmountCalc::from_cubic_bezier(param[0], param[1], param[2], param[3]),
+            _ => return Err(ErrorCurve::BinaryInvalidData),
+        };
+        Ok((amountcalc, reader.offset))
+    }
+}
+
+/// 对齐到 ALIGN, start 为记录起始位置
+fn pad(bytes: &mut Vec<u8>, start: usize) {
+    let len = (bytes.len() - start).div_ceil(ALIGN) * ALIGN + start;
+    bytes.resize(len, 0);
+}
+
+struct BinaryReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ErrorCurve> {
+        let end = self.offset.checked_add(len).filter(|end| *end <= self.bytes.len()).ok_or(ErrorCurve::BinaryUnexpectedEnd)?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+    fn align(&mut self) -> Result<(), ErrorCurve> {
+        let len = self.offset.div_ceil(ALIGN) * ALIGN - self.offset;
+        self.take(len).map(|_| ())
+    }
+    fn u8(&mut self) -> Result<u8, ErrorCurve> {
+        self.value()
+    }
+    fn u16(&mut self) -> Result<u16, ErrorCurve> {
+        self.value()
+    }
+    fn u32(&mut self) -> Result<u32, ErrorCurve> {
+        self.value()
+    }
+    fn value<T: FrameValueBytes>(&mut self) -> Result<T, ErrorCurve> {
+        self.take(T::byte_size()).map(T::read_bytes)
+    }
+    fn keyframe<T: FrameValueBytes + Clone>(&mut self) -> Result<CurveFrameValue<T>, ErrorCurve> {
+        let value = self.value()?;
+        Ok(CurveFrameValue::new(value, [self.value()?, self.value()?]))
+    }
+}
@@ -0,0 +1,373 @@
+//!
+//! 压缩曲线 - 按曲线数值范围量化 (8/16 位), 帧序号差分存储, 四元数 smallest-three 压缩
+//! 采样时只解码前后两个关键帧, 无需解压整条曲线
+
+use core::marker::PhantomData;
+
+use alloc::{vec, vec::Vec};
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{Quaternion, SVector};
+
+use crate::amount::AnimationAmountCalc;
+
+use super::{frame::{FrameDataValue, FrameValueSlerp, KeyFrameCurveScalar, KeyFrameCurveValue}, frame_curve::FrameCurve, FrameIndex, FramePerSecond};
+
+/// 每隔多少个关键帧记录一次完整帧序号
+const FRAME_BLOCK: usize = 16;
+
+/// 可量化的数值 - 拆分为固定数量的 f32 分量
+pub trait FrameValueComponents: Sized {
+    /// 分量数量
+    fn dimension() -> usize;
+    fn write_components(&self, components: &mut [f32]);
+    fn from_components(components: &[f32]) -> Self;
+}
+
+/// 可按 smallest-three 压缩的四元数, 分量顺序为 [x, y, z, w]
+pub trait FrameValueQuaternion: Sized {
+    fn to_xyzw(&self) -> [f32; 4];
+    fn from_xyzw(xyzw: [f32; 4]) -> Self;
+}
+
+/// 量化精度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EQuantization {
+    Bits8,
+    #[default]
+    Bits16,
+}
+
+/// 量化后的分量
+#[derive(Debug, Clone)]
+enum EQuantizedValues {
+    Bits8(Vec<u8>),
+    Bits16(Vec<u16>),
+}
+
+impl EQuantizedValues {
+    fn new(quantization: EQuantization, capacity: usize) -> Self {
+        match quantization {
+            EQuantization::Bits8 => Self::Bits8(Vec::with_capacity(capacity)),
+            EQuantization::Bits16 => Self::Bits16(Vec::with_capacity(capacity)),
+        }
+    }
+    /// 写入 [0, 1] 范围的值
+    fn push(&mut self, normalized: f32) {
+        let normalized = normalized.clamp(0., 1.);
+        match self {
+            Self::Bits8(values) => values.push(libm::roundf(normalized * u8::MAX as f32) as u8),
+            Self::Bits16(values) => values.push(libm::roundf(normalized * u16::MAX as f32) as u16),
+        }
+    }
+    /// 读取 [0, 1] 范围的值
+    fn get(&self, index: usize) -> f32 {
+        match self {
+            Self::Bits8(values) => values[index] as f32 / u8::MAX as f32,
+            Self::Bits16(values) => values[index] as f32 / u16::MAX as f32,
+        }
+    }
+    fn memory_size(&self) -> usize {
+        match self {
+            Self::Bits8(values) => values.len(),
+            Self::Bits16(values) => values.len() * 2,
+        }
+    }
+}
+
+/// 帧序号差分
+#[derive(Debug, Clone)]
+enum EFrameDeltas {
+    Bits8(Vec<u8>),
+    Bits16(Vec<u16>),
+}
+
+/// 差分存储的帧序号, 每 FRAME_BLOCK 个关键帧记录一次完整帧序号以便查找
+#[derive(Debug, Clone)]
+struct CompressedFrames {
+    len: usize,
+    anchors: Vec<FrameIndex>,
+    deltas: EFrameDeltas,
+}
+
+impl CompressedFrames {
+    fn new(frames: &[FrameIndex]) -> Self {
+        let anchors = frames.iter().step_by(FRAME_BLOCK).copied().collect();
+        let deltas = frames.windows(2).map(|pair| pair[1] - pair[0]);
+        let deltas = if frames.windows(2).all(|pair| pair[1] - pair[0] <= u8::MAX as FrameIndex) {
+            EFrameDeltas::Bits8(deltas.map(|delta| delta as u8).collect())
+        } else {
+            EFrameDeltas::Bits16(deltas.collect())
+        };
+        Self { len: frames.len(), anchors, deltas }
+    }
+    fn delta(&self, index: usize) -> FrameIndex {
+        match &self.deltas {
+            EFrameDeltas::Bits8(deltas) => deltas[index] as FrameIndex,
+            EFrameDeltas::Bits16(deltas) => deltas[index],
+        }
+    }
+    fn frame(&self, index: usize) -> FrameIndex {
+        let block = index / FRAME_BLOCK;
+        (block * FRAME_BLOCK..index).fold(self.anchors[block], |frame, index| frame + self.delta(index))
+    }
+    /// 目标帧的前一关键帧 (序号, 帧序号) 与后一关键帧的帧序号, 超出范围时取两端
+    fn locate<F: KeyFrameCurveScalar>(&self, target_frame: F) -> (usize, FrameIndex, FrameIndex) {
+        let block = self.anchors.partition_point(|frame| F::from_frame_index(*frame) <= target_frame).saturating_sub(1);
+        let mut index = block * FRAME_BLOCK;
+        let mut frame = self.anchors[block];
+        while index + 1 < self.len {
+            let next = frame + self.delta(index);
+            if F::from_frame_index(next) > target_frame {
+                return (index, frame, next);
+            }
+            index += 1;
+            frame = next;
+        }
+        (index, frame, frame)
+    }
+    /// 前后关键帧序号与插值进度
+    fn amount<F: KeyFrameCurveScalar>(&self, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> (usize, usize, F) {
+        let (pre, frame1, frame2) = self.locate(target_frame);
+        if frame1 == frame2 {
+            return (pre, pre, F::zero());
+        }
+        let amount = (target_frame - F::from_frame_index(frame1)) / (F::from_frame_index(frame2) - F::from_frame_index(frame1));
+        let amount = amountcalc.calc(amount.clamp(F::zero(), F::one())).clamp(F::zero(), F::one());
+        (pre, pre + 1, amount)
+    }
+    fn memory_size(&self) -> usize {
+        let deltas = match &self.deltas {
+            EFrameDeltas::Bits8(deltas) => deltas.len(),
+            EFrameDeltas::Bits16(deltas) => deltas.len() * 2,
+        };
+        self.anchors.len() * core::mem::size_of::<FrameIndex>() + deltas
+    }
+}
+
+/// 压缩的关键帧曲线 - 关键帧之间线性插值
+/// 由 FrameValues 曲线生成, 其他类型的曲线先烘焙 (`FrameCurve::bake`)
+#[derive(Debug, Clone)]
+pub struct CompressedCurve<T: FrameValueComponents + FrameDataValue<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub design_frame_per_second: FramePerSecond,
+    frames: CompressedFrames,
+    /// 各分量的 (最小值, 范围)
+    ranges: Vec<[f32; 2]>,
+    values: EQuantizedValues,
+    marker: PhantomData<(T, F)>,
+}
+
+impl<T: FrameValueComponents + FrameDataValue<F>, F: KeyFrameCurveScalar> CompressedCurve<T, F> {
+    /// 压缩 FrameValues 曲线, 没有关键帧时返回 None
+    pub fn new(curve: &FrameCurve<T, F>, quantization: EQuantization) -> Option<Self> {
+        let len = curve.frames.len().min(curve.values.len());
+        if len == 0 {
+            return None;
+        }
+        let dimension = T::dimension();
+        let mut components = vec![0f32; len * dimension];
+        for (value, components) in curve.values.iter().zip(components.chunks_mut(dimension)) {
+            value.write_components(components);
+        }
+        let ranges: Vec<[f32; 2]> = (0..dimension).map(|index| {
+            let column = components.iter().skip(index).step_by(dimension);
+            let min = column.clone().fold(f32::MAX, |min, value| min.min(*value));
+            let max = column.fold(f32::MIN, |max, value| max.max(*value));
+            [min, max - min]
+        }).collect();
+
+        let mut values = EQuantizedValues::new(quantization, len * dimension);
+        for (index, component) in components.iter().enumerate() {
+            let [min, range] = ranges[index % dimension];
+            values.push(if range > 0. { (component - min) / range } else { 0. });
+        }
+        Some(Self {
+            design_frame_per_second: curve.design_frame_per_second,
+            frames: CompressedFrames::new(&curve.frames[..len]),
+            ranges,
+            values,
+            marker: PhantomData,
+        })
+    }
+    /// 关键帧数量
+    pub fn len(&self) -> usize {
+        self.frames.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.len == 0
+    }
+    pub fn frame(&self, index: usize) -> FrameIndex {
+        self.frames.frame(index)
+    }
+    /// 解码关键帧数值
+    pub fn value(&self, index: usize) -> T {
+        let dimension = self.ranges.len();
+        let mut components = [0f32; 16];
+        let mut buffer = Vec::new();
+        let components = if dimension <= components.len() {
+            &mut components[..dimension]
+        } else {
+            buffer.resize(dimension, 0.);
+            &mut buffer[..]
+        };
+        for (component, (offset, [min, range])) in components.iter_mut().zip(self.ranges.iter().enumerate()) {
+            *component = min + self.values.get(index * dimension + offset) * range;
+        }
+        T::from_components(components)
+    }
+    /// 在指定时间 (秒) 采样
+    pub fn interple(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> T {
+        let target_frame = time * F::from_frame_index(self.design_frame_per_second);
+        let (pre, next, amount) = self.frames.amount(target_frame, amountcalc);
+        self.value(pre).interpolate(&self.value(next), amount)
+    }
+    /// 占用的内存 (字节), 不含结构体本身
+    pub fn memory_size(&self) -> usize {
+        self.frames.memory_size() + self.ranges.len() * 8 + self.values.memory_size()
+    }
+}
+
+/// 压缩的旋转曲线 - smallest-three 存储四元数, 关键帧之间球面插值
+/// 由 FrameValues 曲线生成
+#[derive(Debug, Clone)]
+pub struct CompressedRotationCurve<T: FrameValueQuaternion + FrameValueSlerp<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub design_frame_per_second: FramePerSecond,
+    frames: CompressedFrames,
+    /// 除最大分量外的三个分量, 范围 [-1/√2, 1/√2]
+    values: EQuantizedValues,
+    /// 最大分量的序号, 每字节 4 个
+    largest: Vec<u8>,
+    marker: PhantomData<(T, F)>,
+}
+
+impl<T: FrameValueQuaternion + FrameValueSlerp<F>, F: KeyFrameCurveScalar> CompressedRotationCurve<T, F> {
+    /// 压缩 FrameValues 曲线, 没有关键帧时返回 None
+    pub fn new(curve: &FrameCurve<T, F>, quantization: EQuantization) -> Option<Self>
+    where
+        T: FrameDataValue<F>,
+    {
+        let len = curve.frames.len().min(curve.values.len());
+        if len == 0 {
+            return None;
+        }
+        let mut values = EQuantizedValues::new(quantization, len * 3);
+        let mut largest = vec![0u8; len.div_ceil(4)];
+        for (index, value) in curve.values[..len].iter().enumerate() {
+            let mut xyzw = value.to_xyzw();
+            let length = libm::sqrtf(xyzw.iter().map(|value| value * value).sum());
+            if length > 0. {
+                xyzw.iter_mut().for_each(|value| *value /= length);
+            }
+            let max = (0..4).fold(0, |max, index| if xyzw[index].abs() > xyzw[max].abs() { index } else { max });
+            // q 与 -q 表示同一旋转, 使最大分量为正以省略其符号
+            let sign = if xyzw[max] < 0. { -1. } else { 1. };
+            for (component, value) in xyzw.iter().enumerate() {
+                if component != max {
+                    values.push((value * sign * core::f32::consts::SQRT_2 + 1.) * 0.5);
+                }
+            }
+            largest[index / 4] |= (max as u8) << ((index % 4) * 2);
+        }
+        Some(Self {
+            design_frame_per_second: curve.design_frame_per_second,
+            frames: CompressedFrames::new(&curve.frames[..len]),
+            values,
+            largest,
+            marker: PhantomData,
+        })
+    }
+    pub fn len(&self) -> usize {
+        self.frames.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.len == 0
+    }
+    pub fn frame(&self, index: usize) -> FrameIndex {
+        self.frames.frame(index)
+    }
+    /// 解码关键帧数值
+    pub fn value(&self, index: usize) -> T {
+        let max = ((self.largest[index / 4] >> ((index % 4) * 2)) & 0b11) as usize;
+        let mut xyzw = [0f32; 4];
+        let mut offset = index * 3;
+        let mut squared = 0.;
+        for (component, value) in xyzw.iter_mut().enumerate() {
+            if component != max {
+                *value = (self.values.get(offset) * 2. - 1.) * core::f32::consts::FRAC_1_SQRT_2;
+                squared += *value * *value;
+                offset += 1;
+            }
+        }
+        xyzw[max] = libm::sqrtf((1. - squared).max(0.));
+        T::from_xyzw(xyzw)
+    }
+    /// 在指定时间 (秒) 采样
+    pub fn interple(&self, time: F, amountcalc: &AnimationAmountCalc<F>) -> T {
+        let target_frame = time * F::from_frame_index(self.design_frame_per_second);
+        let (pre, next, amount) = self.frames.amount(target_frame, amountcalc);
+        self.value(pre).slerp(&self.value(next), amount)
+    }
+    /// 占用的内存 (字节), 不含结构体本身
+    pub fn memory_size(&self) -> usize {
+        self.frames.memory_size() + self.values.memory_size() + self.largest.len()
+    }
+}
+
+/// f32 / f64
+macro_rules! impl_frame_value_components_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl FrameValueComponents for $ty {
+                fn dimension() -> usize {
+                    1
+                }
+                fn write_components(&self, components: &mut [f32]) {
+                    components[0] = *self as f32;
+                }
+                fn from_components(components: &[f32]) -> Self {
+                    components[0] as $ty
+                }
+            }
+
+            /// 定长数组
+            impl<const N: usize> FrameValueComponents for [$ty; N] {
+                fn dimension() -> usize {
+                    N
+                }
+                fn write_components(&self, components: &mut [f32]) {
+                    components.iter_mut().zip(self.iter()).for_each(|(component, value)| *component = *value as f32);
+                }
+                fn from_components(components: &[f32]) -> Self {
+                    core::array::from_fn(|index| components[index] as $ty)
+                }
+            }
+
+            #[cfg(feature = "nalgebra")]
+            /// Vector - 任意维度
+            impl<const D: usize> FrameValueComponents for SVector<$ty, D> {
+                fn dimension() -> usize {
+                    D
+                }
+                fn write_components(&self, components: &mut [f32]) {
+                    components.iter_mut().zip(self.iter()).for_each(|(component, value)| *component = *value as f32);
+                }
+                fn from_components(components: &[f32]) -> Self {
+                    SVector::from_fn(|index, _| components[index] as $ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_components_scalar!(f32, f64);
+
+#[cfg(feature = "nalgebra")]
+/// Quaternion - coords 顺序即 [x, y, z, w]
+impl FrameValueQuaternion for Quaternion<f32> {
+    fn to_xyzw(&self) -> [f32; 4] {
+        [self.coords.x, self.coords.y, self.coords.z, self.coords.w]
+    }
+    fn from_xyzw(xyzw: [f32; 4]) -> Self {
+        Quaternion::new(xyzw[3], xyzw[0], xyzw[1], xyzw[2])
+    }
+}
@@ -1,11 +1,11 @@
-use crate::{curve::{frame_curve::FrameCurve, frame::{FrameDataValue, KeyFrameCurveValue}}, bezier, amount::AnimationAmountCalc};
+use crate::{curve::{frame_curve::FrameCurve, frame::{FrameDataValue, KeyFrameCurveScalar}}, bezier, amount::AnimationAmountCalc};
 
 
-pub fn interplate_cubebezier<T: FrameDataValue>(curve: &FrameCurve<T>, target_frame: f32, _amountcalc: &AnimationAmountCalc) -> T {
-    let amount = KeyFrameCurveValue::clamp(
-        target_frame / curve.frame_number as KeyFrameCurveValue,
-        0.,
-        1.,
+pub fn interplate_cubebezier<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(curve: &FrameCurve<T, F>, target_frame: F, _amountcalc: &AnimationAmountCalc<F>) -> T {
+    let amount = F::clamp(
+        target_frame / F::from_frame_index(curve.frame_number),
+        F::zero(),
+        F::one(),
     );
 
     let amount = bezier::cubic_bezier(
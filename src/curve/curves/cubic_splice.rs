@@ -1,11 +1,13 @@
-use crate::{amount::AnimationAmountCalc, curve::{curves::FrameCurve, frame::{FrameDataValue, KeyFrameCurveValue}, FrameIndex}};
+use alloc::vec::Vec;
+
+use crate::{amount::AnimationAmountCalc, curve::{curves::FrameCurve, frame::{FrameDataValue, KeyFrameCurveScalar}, FrameIndex}};
 
 use super::get_pre_next_frame_index;
 
 
-pub fn interplate_cubic_splice<T: FrameDataValue>(curve: &FrameCurve<T>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> T {
+pub fn interplate_cubic_splice<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(curve: &FrameCurve<T, F>, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> T {
 
-    let (pre, next, amount, frame_delta) = _interplate_cubic_splice_amount(&curve.frames, target_frame, amountcalc, curve.design_frame_per_second as KeyFrameCurveValue);
+    let (pre, next, amount, frame_delta) = _interplate_cubic_splice_amount(&curve.frames, target_frame, amountcalc, F::from_frame_index(curve.design_frame_per_second));
 
     let value1 = curve.cubic_spline_values[pre].value();
     let value2 = curve.cubic_spline_values[next].value();
@@ -16,24 +18,24 @@ pub fn interplate_cubic_splice<T: FrameDataValue>(curve: &FrameCurve<T>, target_
     T::hermite(value1, tangent1, value2, tangent2, amount, frame_delta)
 }
 
-fn _interplate_cubic_splice_amount(frames: &Vec<FrameIndex>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc, design_frame_per_second: KeyFrameCurveValue) -> (usize, usize, KeyFrameCurveValue, KeyFrameCurveValue) {
+fn _interplate_cubic_splice_amount<F: KeyFrameCurveScalar>(frames: &Vec<FrameIndex>, target_frame: F, amountcalc: &AnimationAmountCalc<F>, design_frame_per_second: F) -> (usize, usize, F, F) {
     let (pre, next) = get_pre_next_frame_index(frames, target_frame);
 
     let frame1 = frames[pre];
     let frame2 = frames[next];
 
-    let mut frame_delta = frame2 as KeyFrameCurveValue - frame1 as KeyFrameCurveValue;
+    let mut frame_delta = F::from_frame_index(frame2) - F::from_frame_index(frame1);
 
     let amount = if frame1 == frame2 {
-        0.0
+        F::zero()
     } else {
-        KeyFrameCurveValue::clamp(
+        F::clamp(
             amountcalc.calc(
-            (target_frame - frame1 as KeyFrameCurveValue)
+            (target_frame - F::from_frame_index(frame1))
                 / frame_delta
             ),
-            0.,
-            1.,
+            F::zero(),
+            F::one(),
         )
     };
 
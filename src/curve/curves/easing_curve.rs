@@ -1,23 +1,28 @@
-use crate::{curve::{frame_curve::FrameCurve, frame::{FrameDataValue, KeyFrameCurveValue}}, amount::AnimationAmountCalc};
+use crate::{easing::function::{easing_call, get_easing_call}, curve::{frame_curve::FrameCurve, frame::{FrameDataValue, KeyFrameCurveScalar}}, amount::AnimationAmountCalc};
 
 
 
-pub fn interplate_easing<T: FrameDataValue>(curve: &FrameCurve<T>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> T {
+pub fn interplate_easing<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(curve: &FrameCurve<T, F>, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> T {
     // log::trace!(
     //     "easing, target_frame: {}, frame_number: {}",
     //     target_frame,
     //     curve.frame_number
     // );
-    let mut amount = KeyFrameCurveValue::clamp(
+    let mut amount = F::clamp(
         amountcalc.calc(
-            target_frame / curve.frame_number as KeyFrameCurveValue
+            target_frame / F::from_frame_index(curve.frame_number)
         ),
-        0.,
-        1.,
+        F::zero(),
+        F::one(),
     );
 
-    let call = &curve.easing;
-    amount = call(amount);
+    // 兼容被替换的缓动函数
+    #[allow(deprecated)]
+    if core::ptr::fn_addr_eq(curve.easing, get_easing_call::<F>(curve.easing_mode())) {
+        amount = easing_call(amount, &curve.easing_mode());
+    } else {
+        amount = (curve.easing)(amount);
+    }
 
     curve.value_offset.as_ref().unwrap().append(curve.value_scalar.as_ref().unwrap(), amount)
 }
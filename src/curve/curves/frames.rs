@@ -1,10 +1,12 @@
-use crate::{amount::AnimationAmountCalc, curve::{curves::get_pre_next_frame_index, frame::{FrameDataValue, KeyFrameCurveValue}, FrameIndex}};
+use alloc::vec::Vec;
+
+use crate::{amount::AnimationAmountCalc, curve::{curves::get_pre_next_frame_index, frame::{FrameDataValue, KeyFrameCurveScalar}, FrameIndex}};
 
 use super::FrameCurve;
 
 
 
-pub fn interplate_frame_values<T: FrameDataValue>(curve: &FrameCurve<T>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> T {
+pub fn interplate_frame_values<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(curve: &FrameCurve<T, F>, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> T {
     let (pre, next, amount) = _interplate_frame_values_amount(&curve.frames, target_frame, amountcalc);
     // let value1 = curve.values.get(pre).unwrap();
     // let value2 = curve.values.get(next).unwrap();
@@ -13,32 +15,32 @@ pub fn interplate_frame_values<T: FrameDataValue>(curve: &FrameCurve<T>, target_
 }
 
 
-pub fn interplate_frame_values_step<T: FrameDataValue>(curve: &FrameCurve<T>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> T {
+pub fn interplate_frame_values_step<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(curve: &FrameCurve<T, F>, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> T {
     let (pre, next, amount) = _interplate_frame_values_amount(&curve.frames, target_frame, amountcalc);
 
-    if amount < 0.5 {
+    if amount < F::from_f64_value(0.5) {
         curve.values[pre].clone()
     } else {
         curve.values[next].clone()
     }
 }
 
-fn _interplate_frame_values_amount(frames: &Vec<FrameIndex>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> (usize, usize, KeyFrameCurveValue) {
+fn _interplate_frame_values_amount<F: KeyFrameCurveScalar>(frames: &Vec<FrameIndex>, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> (usize, usize, F) {
     let (pre, next) = get_pre_next_frame_index(frames, target_frame);
     let frame1 = frames[pre];
 
     let frame2 = frames[next];
 
     let amount = if frame1 == frame2 {
-        0.0
+        F::zero()
     } else {
-        KeyFrameCurveValue::clamp(
+        F::clamp(
             amountcalc.calc(
-                (target_frame - frame1 as KeyFrameCurveValue)
-                / (frame2 as KeyFrameCurveValue - frame1 as KeyFrameCurveValue)
+                (target_frame - F::from_frame_index(frame1))
+                / (F::from_frame_index(frame2) - F::from_frame_index(frame1))
             ),
-            0.,
-            1.,
+            F::zero(),
+            F::one(),
         )
     };
 
@@ -1,14 +1,16 @@
-use crate::{amount::AnimationAmountCalc, curve::{curves::get_pre_next_frame_index, frame::{CurveFrameValue, FrameDataValue, KeyFrameCurveValue}, FrameIndex}, hermite};
+use alloc::vec::Vec;
+
+use crate::{amount::AnimationAmountCalc, curve::{curves::get_pre_next_frame_index, frame::{CurveFrameValue, FrameDataValue, KeyFrameCurveScalar}, FrameIndex}, hermite};
 
 use super::FrameCurve;
 
 
-pub fn interplate_minmaxcurve<T: FrameDataValue>(curve: &FrameCurve<T>, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> T {
+pub fn interplate_minmaxcurve<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(curve: &FrameCurve<T, F>, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> T {
     let amount = _interplate_minmaxcurve_amount(&curve.frames, target_frame, &curve.minmax_curve_values, amountcalc);
     curve.value_offset.as_ref().unwrap().append(curve.value_scalar.as_ref().unwrap(), amount)
 }
 
-fn _interplate_minmaxcurve_amount(frames: &Vec<FrameIndex>, target_frame: KeyFrameCurveValue, minmax_curve_values: &Vec<CurveFrameValue<KeyFrameCurveValue>>, amountcalc: &AnimationAmountCalc) -> KeyFrameCurveValue {
+fn _interplate_minmaxcurve_amount<F: KeyFrameCurveScalar>(frames: &Vec<FrameIndex>, target_frame: F, minmax_curve_values: &Vec<CurveFrameValue<F>>, amountcalc: &AnimationAmountCalc<F>) -> F {
     let (pre, next) = get_pre_next_frame_index(frames, target_frame);
 
     let frame1 = frames[pre];
@@ -21,15 +23,15 @@ fn _interplate_minmaxcurve_amount(frames: &Vec<FrameIndex>, target_frame: KeyFra
     let tangent2 = minmax_curve_values[next].intangent();
 
     let amount = if frame1 == frame2 {
-        0.0
+        F::zero()
     } else {
-        KeyFrameCurveValue::clamp(
+        F::clamp(
             amountcalc.calc(
-            (target_frame - frame1 as KeyFrameCurveValue)
-                / (frame2 as KeyFrameCurveValue - frame1 as KeyFrameCurveValue)
+            (target_frame - F::from_frame_index(frame1))
+                / (F::from_frame_index(frame2) - F::from_frame_index(frame1))
             ),
-            0.,
-            1.,
+            F::zero(),
+            F::one(),
         )
     };
 
@@ -1,10 +1,12 @@
-use std::fmt::Debug;
+use core::fmt::Debug;
+
+use alloc::{vec, vec::Vec};
 
 use crate::{easing::{EEasingMode, function::get_easing_call}, amount::AnimationAmountCalc};
 
-use self::{easing_curve::interplate_easing, frames::interplate_frame_values, minmax_curve::interplate_minmaxcurve, cubic_splice::interplate_cubic_splice, cubic_bezier_curve::interplate_cubebezier};
+use self::{easing_curve::interplate_easing, frames::{interplate_frame_values, interplate_frame_values_step}, minmax_curve::interplate_minmaxcurve, cubic_splice::interplate_cubic_splice, cubic_bezier_curve::interplate_cubebezier};
 
-use super::{frame::{FrameDataValue, KeyFrameCurveValue, CurveFrameValue}, FrameIndex, FramePerSecond};
+use super::{frame::{FrameDataValue, KeyFrameCurveScalar, KeyFrameCurveValue, CurveFrameValue}, FrameIndex, FramePerSecond};
 
 pub mod frames;
 pub mod cubic_bezier_curve;
@@ -12,7 +14,7 @@ pub mod cubic_splice;
 pub mod minmax_curve;
 pub mod easing_curve;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EFrameCurveType {
     /// 关键帧数值 - Linear
     /// 帧数据数组[ frameIndex[], value[] ]
@@ -34,9 +36,11 @@ pub enum EFrameCurveType {
     GLTFCubicSpline = 0x05,
 }
 
-pub struct FrameCurve<T: FrameDataValue> {
+pub struct FrameCurve<T: FrameDataValue<F>, F: KeyFrameCurveScalar = KeyFrameCurveValue> {
     /// 设计每秒多少帧
     pub design_frame_per_second: FramePerSecond,
+    /// 曲线类型
+    curve_type: EFrameCurveType,
 
     /// 动画目标数据的起始值
     pub value_offset: Option<T>,
@@ -46,13 +50,13 @@ pub struct FrameCurve<T: FrameDataValue> {
     /// 缓动类型 [Easing 缓动类型]
     easing_mode: EEasingMode,
     /// 曲线拓展数据 [CubicBezier的参数]
-    cubic_bezier_args: [KeyFrameCurveValue; 4],
+    cubic_bezier_args: [F; 4],
 
     /// 帧序号值
     pub frames: Vec<FrameIndex>,
 
     /// For MinMaxCurve
-    pub minmax_curve_values: Vec<CurveFrameValue<KeyFrameCurveValue>>,
+    pub minmax_curve_values: Vec<CurveFrameValue<F>>,
 
     /// For FrameValues | FrameValuesStep
     pub values: Vec<T>,
@@ -65,19 +69,24 @@ pub struct FrameCurve<T: FrameDataValue> {
     pub max_frame: FrameIndex,
     /// 动画帧数
     pub frame_number: FrameIndex,
-    pub call: fn(&Self, KeyFrameCurveValue, &AnimationAmountCalc) -> T,
-    pub easing: fn(KeyFrameCurveValue) -> KeyFrameCurveValue,
+    /// 插值函数, 默认为曲线类型对应的函数, `interple` 以 match 分发; 被替换时 `interple` 调用替换后的函数
+    #[deprecated(note = "`interple` 已按曲线类型分发, 无需设置此函数")]
+    pub call: fn(&Self, F, &AnimationAmountCalc<F>) -> T,
+    /// 缓动函数, 默认为 `easing_mode` 对应的函数; 被替换时 Easing 曲线调用替换后的函数
+    #[deprecated(note = "Easing 曲线已按 `easing_mode` 分发, 无需设置此函数")]
+    pub easing: fn(F) -> F,
 }
 
-impl<F: FrameDataValue> AsRef<FrameCurve<F>> for FrameCurve<F> {
-    fn as_ref(&self) -> &FrameCurve<F> {
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> AsRef<FrameCurve<T, F>> for FrameCurve<T, F> {
+    fn as_ref(&self) -> &FrameCurve<T, F> {
         self
     }
 }
 
-impl<T: Debug + FrameDataValue> Debug for FrameCurve<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T: Debug + FrameDataValue<F>, F: KeyFrameCurveScalar> Debug for FrameCurve<T, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("FrameCurve")
+            .field("curve_type", &self.curve_type)
             .field("easing_mode", &self.easing_mode)
             .field("cubic_bezier_args", &self.cubic_bezier_args)
             .field("design_frame_per_second", &self.design_frame_per_second)
@@ -93,25 +102,59 @@ impl<T: Debug + FrameDataValue> Debug for FrameCurve<T> {
     }
 }
 
-impl<T: FrameDataValue> FrameCurve<T> {
+// 构造时初始化兼容保留的 `call` / `easing`
+#[allow(deprecated)]
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> FrameCurve<T, F> {
+    pub fn curve_type(&self) -> EFrameCurveType {
+        self.curve_type
+    }
+    pub fn easing_mode(&self) -> EEasingMode {
+        self.easing_mode
+    }
+    pub fn cubic_bezier_args(&self) -> [F; 4] {
+        self.cubic_bezier_args
+    }
     pub fn size(&self) -> usize {
         1 + 1 + 4 * 4 + 2 + 2 + 2 + 2 + 8 + 8
     }
-    pub fn interple(&self, target_frame: KeyFrameCurveValue, amountcalc: &AnimationAmountCalc) -> T {
-        let call = &self.call;
-        let target_frame = target_frame * self.design_frame_per_second as KeyFrameCurveValue;
-        call(&self, target_frame, amountcalc)
+    /// 按曲线类型分发, 各插值函数可被内联
+    pub fn interple(&self, target_frame: F, amountcalc: &AnimationAmountCalc<F>) -> T {
+        let target_frame = target_frame * F::from_frame_index(self.design_frame_per_second);
+        if !core::ptr::fn_addr_eq(self.call, Self::type_call(self.curve_type)) {
+            return (self.call)(self, target_frame, amountcalc);
+        }
+        match self.curve_type {
+            EFrameCurveType::FrameValues        => interplate_frame_values(self, target_frame, amountcalc),
+            EFrameCurveType::FrameValuesStep    => interplate_frame_values_step(self, target_frame, amountcalc),
+            EFrameCurveType::EasingCurve        => interplate_easing(self, target_frame, amountcalc),
+            EFrameCurveType::MinMaxCurve        => interplate_minmaxcurve(self, target_frame, amountcalc),
+            EFrameCurveType::CubicBezierCurve   => interplate_cubebezier(self, target_frame, amountcalc),
+            EFrameCurveType::GLTFCubicSpline    => interplate_cubic_splice(self, target_frame, amountcalc),
+        }
+    }
+
+    /// 曲线类型对应的插值函数
+    fn type_call(curve_type: EFrameCurveType) -> fn(&Self, F, &AnimationAmountCalc<F>) -> T {
+        match curve_type {
+            EFrameCurveType::FrameValues        => interplate_frame_values::<T, F>,
+            EFrameCurveType::FrameValuesStep    => interplate_frame_values_step::<T, F>,
+            EFrameCurveType::EasingCurve        => interplate_easing::<T, F>,
+            EFrameCurveType::MinMaxCurve        => interplate_minmaxcurve::<T, F>,
+            EFrameCurveType::CubicBezierCurve   => interplate_cubebezier::<T, F>,
+            EFrameCurveType::GLTFCubicSpline    => interplate_cubic_splice::<T, F>,
+        }
     }
     
     /// 曲线 - 线性插值帧 - 无曲线描述,仅关键 帧-值
     ///
-    pub fn curve_frame_values(design_frame_per_second: FramePerSecond) -> FrameCurve<T> {
+    pub fn curve_frame_values(design_frame_per_second: FramePerSecond) -> FrameCurve<T, F> {
         FrameCurve {
             design_frame_per_second,
+            curve_type: EFrameCurveType::FrameValues,
             value_offset: None,
             value_scalar: None,
             easing_mode: EEasingMode::None,
-            cubic_bezier_args: [0., 0., 1., 1.],
+            cubic_bezier_args: [F::zero(), F::zero(), F::one(), F::one()],
             frames: vec![],
             minmax_curve_values: vec![],
             values: vec![],
@@ -119,7 +162,7 @@ impl<T: FrameDataValue> FrameCurve<T> {
             min_frame: FrameIndex::MAX,
             max_frame: FrameIndex::MIN,
             frame_number: 0 as FrameIndex,
-            call: interplate_frame_values::<T>,
+            call: interplate_frame_values::<T, F>,
             easing: get_easing_call(EEasingMode::None),
         }
     }
@@ -142,15 +185,26 @@ impl<T: FrameDataValue> FrameCurve<T> {
         self.max_frame = max;
         self.frame_number = max - min;
     }
+    /// 曲线 - 阶跃帧 - 无曲线描述,仅关键 帧-值, 取距离最近的关键帧数值
+    /// 关键帧使用 `curve_frame_values_frame` 添加
+    ///
+    pub fn curve_frame_values_step(design_frame_per_second: FramePerSecond) -> FrameCurve<T, F> {
+        FrameCurve {
+            curve_type: EFrameCurveType::FrameValuesStep,
+            call: interplate_frame_values_step::<T, F>,
+            ..Self::curve_frame_values(design_frame_per_second)
+        }
+    }
     /// 曲线 - 线性插值帧 - 无曲线描述,仅关键 帧-值
     ///
-    pub fn curve_cubic_spline(design_frame_per_second: FramePerSecond) -> FrameCurve<T> {
+    pub fn curve_cubic_spline(design_frame_per_second: FramePerSecond) -> FrameCurve<T, F> {
         FrameCurve {
             design_frame_per_second,
+            curve_type: EFrameCurveType::GLTFCubicSpline,
             value_offset: None,
             value_scalar: None,
             easing_mode: EEasingMode::None,
-            cubic_bezier_args: [0., 0., 1., 1.],
+            cubic_bezier_args: [F::zero(), F::zero(), F::one(), F::one()],
             frames: vec![],
             minmax_curve_values: vec![],
             values: vec![],
@@ -158,7 +212,7 @@ impl<T: FrameDataValue> FrameCurve<T> {
             min_frame: FrameIndex::MAX,
             max_frame: FrameIndex::MIN,
             frame_number: 0 as FrameIndex,
-            call: interplate_cubic_splice::<T>,
+            call: interplate_cubic_splice::<T, F>,
             easing: get_easing_call(EEasingMode::None),
         }
     }
@@ -193,13 +247,14 @@ impl<T: FrameDataValue> FrameCurve<T> {
         from: T,
         scalar: T,
         design_frame_per_second: FramePerSecond,
-    ) -> FrameCurve<T> {
+    ) -> FrameCurve<T, F> {
         FrameCurve {
             design_frame_per_second,
+            curve_type: EFrameCurveType::MinMaxCurve,
             value_offset: Some(from),
             value_scalar: Some(scalar),
             easing_mode: EEasingMode::None,
-            cubic_bezier_args: [0., 0., 1., 1.],
+            cubic_bezier_args: [F::zero(), F::zero(), F::one(), F::one()],
             frames: vec![],
             minmax_curve_values: vec![],
             values: vec![],
@@ -207,7 +262,7 @@ impl<T: FrameDataValue> FrameCurve<T> {
             min_frame: FrameIndex::MAX,
             max_frame: FrameIndex::MIN,
             frame_number: 0 as FrameIndex,
-            call: interplate_minmaxcurve::<T>,
+            call: interplate_minmaxcurve::<T, F>,
             easing: get_easing_call(EEasingMode::None),
         }
     }
@@ -223,9 +278,9 @@ impl<T: FrameDataValue> FrameCurve<T> {
     pub fn curve_minmax_curve_frame(
         &mut self,
         frame: FrameIndex,
-        value: KeyFrameCurveValue,
-        intangent: KeyFrameCurveValue,
-        outtangent: KeyFrameCurveValue,
+        value: F,
+        intangent: F,
+        outtangent: F,
     ) {
         let keyframe = CurveFrameValue::new(value, [intangent, outtangent]);
 
@@ -255,13 +310,14 @@ impl<T: FrameDataValue> FrameCurve<T> {
         frame_count: FrameIndex,
         design_frame_per_second: FramePerSecond,
         easing_mode: EEasingMode,
-    ) -> FrameCurve<T> {
+    ) -> FrameCurve<T, F> {
         FrameCurve {
             design_frame_per_second,
+            curve_type: EFrameCurveType::EasingCurve,
             value_offset: Some(from),
             value_scalar: Some(scalar),
             easing_mode,
-            cubic_bezier_args: [0., 0., 1., 1.],
+            cubic_bezier_args: [F::zero(), F::zero(), F::one(), F::one()],
             frames: vec![],
             minmax_curve_values: vec![],
             values: vec![],
@@ -269,7 +325,7 @@ impl<T: FrameDataValue> FrameCurve<T> {
             min_frame: 0 as FrameIndex,
             max_frame: frame_count,
             frame_number: frame_count,
-            call: interplate_easing::<T>,
+            call: interplate_easing::<T, F>,
             easing: get_easing_call(easing_mode),
         }
     }
@@ -285,13 +341,14 @@ impl<T: FrameDataValue> FrameCurve<T> {
         scalar: T,
         frame_count: FrameIndex,
         design_frame_per_second: FramePerSecond,
-        x1: KeyFrameCurveValue,
-        y1: KeyFrameCurveValue,
-        x2: KeyFrameCurveValue,
-        y2: KeyFrameCurveValue,
-    ) -> FrameCurve<T> {
+        x1: F,
+        y1: F,
+        x2: F,
+        y2: F,
+    ) -> FrameCurve<T, F> {
         FrameCurve {
             design_frame_per_second,
+            curve_type: EFrameCurveType::CubicBezierCurve,
             value_offset: Some(from),
             value_scalar: Some(scalar),
             easing_mode: EEasingMode::None,
@@ -303,7 +360,7 @@ impl<T: FrameDataValue> FrameCurve<T> {
             min_frame: 0 as FrameIndex,
             max_frame: frame_count,
             frame_number: frame_count,
-            call: interplate_cubebezier::<T>,
+            call: interplate_cubebezier::<T, F>,
             easing: get_easing_call(EEasingMode::None),
         }
     }
@@ -311,11 +368,11 @@ impl<T: FrameDataValue> FrameCurve<T> {
     /// 获取目标帧的前后帧在帧数组中的序号
     pub fn get_pre_next_frame_index(
         frames: &Vec<FrameIndex>,
-        target_frame: KeyFrameCurveValue,
+        target_frame: F,
     ) -> (usize, usize) {
         let total_num = frames.len();
         let index = frames
-            .binary_search(&(target_frame as FrameIndex))
+            .binary_search(&target_frame.to_frame_index())
             .unwrap_or_else(|x| x);
         if index == 0 {
             // println!("AA {}, {}", index, target_frame);
@@ -332,13 +389,13 @@ impl<T: FrameDataValue> FrameCurve<T> {
 
 /// 获取目标帧的前后帧在帧数组中的序号
 #[inline]
-pub fn get_pre_next_frame_index(
+pub fn get_pre_next_frame_index<F: KeyFrameCurveScalar>(
     frames: &Vec<FrameIndex>,
-    target_frame: KeyFrameCurveValue,
+    target_frame: F,
 ) -> (usize, usize) {
     let total_num = frames.len();
     let index = frames
-        .binary_search(&(target_frame as FrameIndex))
+        .binary_search(&target_frame.to_frame_index())
         .unwrap_or_else(|x| x);
     if index == 0 {
         (index, index)
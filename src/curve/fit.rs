@@ -0,0 +1,292 @@
+//!
+//! 曲线拟合 - 以最小二乘将采样序列拟合为尽量少的三次关键帧
+//!
+//! 每段固定首尾采样值, 拟合切线 (及权重); 误差超限时在误差最大的采样处拆分后分别拟合
+
+use alloc::{vec, vec::Vec};
+
+use crate::bezier;
+
+use super::{frame::{FrameDataValue, KeyFrameCurveScalar, KeyFrameCurveValue}, frame_curve::FrameCurve, reduce::{ReduceStats, ReduceTolerance}, FrameIndex, FramePerSecond};
+
+/// 拟合权重时 参数求解 - 最小二乘 的迭代次数
+const WEIGHT_ITERATIONS: usize = 4;
+/// 权重的取值范围, 保证时间单调
+const MIN_WEIGHT: f64 = 0.01;
+const MAX_WEIGHT: f64 = 1.0;
+
+/// 三次 Hermite 段的拟合结果
+struct HermiteSegment<T> {
+    from: usize,
+    /// 首尾切线 (每秒)
+    tangents: [T; 2],
+}
+
+/// 拟合为 GLTFCubicSpline 曲线
+///
+/// * [samples] - (帧序号, 值), 按帧序号升序
+/// * [design_frame_per_second] - 帧率
+/// * [tolerance] - 允许的误差
+/// * [distance] - 两个值之间的距离, 如 `|a, b| (a - b).abs()`
+///
+/// 关键帧取在采样处, 拆分处的入切线与出切线相互独立
+pub fn fit_cubic_spline<T: FrameDataValue<F>, F: KeyFrameCurveScalar, D: Fn(&T, &T) -> F>(samples: &[(FrameIndex, T)], design_frame_per_second: FramePerSecond, tolerance: ReduceTolerance<F>, distance: D) -> (FrameCurve<T, F>, ReduceStats<F>) {
+    let tolerance = tolerance_value(samples, tolerance, &distance);
+    let fps = F::from_frame_index(design_frame_per_second);
+    let (segments, max_error) = fit_segments(samples.len(), tolerance, |from, to| {
+        let tangents = fit_hermite(samples, from, to, fps);
+        let error = hermite_error(samples, from, to, fps, &tangents, &distance);
+        (HermiteSegment { from, tangents }, error)
+    });
+
+    let mut result = FrameCurve::curve_cubic_spline(design_frame_per_second);
+    for (index, segment) in segments.iter().enumerate() {
+        let (frame, value) = &samples[segment.from];
+        let intangent = if index == 0 { &segment.tangents[0] } else { &segments[index - 1].tangents[1] };
+        result.curve_cubic_splice_frame(*frame, value.clone(), intangent.clone(), segment.tangents[0].clone());
+    }
+    match (segments.last(), samples.last()) {
+        (Some(segment), Some((frame, value))) => result.curve_cubic_splice_frame(*frame, value.clone(), segment.tangents[1].clone(), segment.tangents[1].clone()),
+        (None, Some((frame, value))) => {
+            let zero = value.append(value, -F::one());
+            result.curve_cubic_splice_frame(*frame, value.clone(), zero.clone(), zero);
+        },
+        _ => {},
+    }
+    let keys_after = result.frames.len();
+    (result, ReduceStats { keys_before: samples.len(), keys_after, max_error })
+}
+
+/// 带权重的贝塞尔关键帧
+/// 切线为每秒斜率, 权重为控制点在时间上占相邻区间的比例, 均为 1/3 时与 Hermite 相同
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedBezierKey<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub frame: FrameIndex,
+    pub value: F,
+    pub intangent: F,
+    pub outtangent: F,
+    pub inweight: F,
+    pub outweight: F,
+}
+
+/// 带权重的贝塞尔关键帧曲线
+#[derive(Debug, Clone)]
+pub struct WeightedBezierCurve<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    pub design_frame_per_second: FramePerSecond,
+    /// 按帧序号升序
+    pub keys: Vec<WeightedBezierKey<F>>,
+}
+
+impl<F: KeyFrameCurveScalar> WeightedBezierCurve<F> {
+    pub fn new(design_frame_per_second: FramePerSecond) -> Self {
+        Self { design_frame_per_second, keys: Vec::new() }
+    }
+    /// 指定时间 (秒) 的值, 超出范围取两端的值, 没有关键帧时为 0
+    pub fn interple(&self, time: F) -> F {
+        let frame = time * F::from_frame_index(self.design_frame_per_second);
+        let next = self.keys.partition_point(|key| F::from_frame_index(key.frame) <= frame);
+        if next == 0 {
+            return self.keys.first().map_or(F::zero(), |key| key.value);
+        }
+        if next == self.keys.len() {
+            return self.keys[next - 1].value;
+        }
+        let (from, to) = (&self.keys[next - 1], &self.keys[next]);
+        let frames = F::from_frame_index(to.frame - from.frame);
+        let seconds = frames / F::from_frame_index(self.design_frame_per_second);
+        let amount = (frame - F::from_frame_index(from.frame)) / frames;
+        let controls = [
+            from.value,
+            from.value + from.outtangent * seconds * from.outweight,
+            to.value - to.intangent * seconds * to.inweight,
+            to.value,
+        ];
+        bezier_value(controls, bezier::cubic_bezier_solve(from.outweight, F::one() - to.inweight, amount))
+    }
+}
+
+/// 拟合为带权重的贝塞尔关键帧曲线
+///
+/// * [samples] - (帧序号, 值), 按帧序号升序
+/// * [design_frame_per_second] - 帧率
+/// * [tolerance] - 允许的误差
+///
+/// 先以 Hermite 拟合切线, 再保持切线方向拟合权重
+pub fn fit_weighted_bezier<F: KeyFrameCurveScalar + FrameDataValue<F>>(samples: &[(FrameIndex, F)], design_frame_per_second: FramePerSecond, tolerance: ReduceTolerance<F>) -> (WeightedBezierCurve<F>, ReduceStats<F>) {
+    let distance = |a: &F, b: &F| (*a - *b).abs();
+    let tolerance = tolerance_value(samples, tolerance, &distance);
+    let fps = F::from_frame_index(design_frame_per_second);
+    let (segments, max_error) = fit_segments(samples.len(), tolerance, |from, to| {
+        let tangents = fit_hermite(samples, from, to, fps);
+        let third = F::one() / F::from_f64_value(3.);
+        let hermite = (hermite_error(samples, from, to, fps, &tangents, &distance), [third, third]);
+        let weighted = fit_weights(samples, from, to, fps, &tangents);
+        let (error, weights) = if weighted.0 < hermite.0 { weighted } else { hermite };
+        ((from, to, tangents, weights), error)
+    });
+
+    let mut result = WeightedBezierCurve::new(design_frame_per_second);
+    let key = |index: usize| WeightedBezierKey { frame: samples[index].0, value: samples[index].1, intangent: F::zero(), outtangent: F::zero(), inweight: F::one() / F::from_f64_value(3.), outweight: F::one() / F::from_f64_value(3.) };
+    for (from, to, tangents, weights) in segments.iter() {
+        if result.keys.is_empty() {
+            let mut first = key(*from);
+            first.intangent = tangents[0];
+            result.keys.push(first);
+        }
+        if let Some(last) = result.keys.last_mut() {
+            last.outtangent = tangents[0];
+            last.outweight = weights[0];
+        }
+        let mut next = key(*to);
+        next.intangent = tangents[1];
+        next.outtangent = tangents[1];
+        next.inweight = weights[1];
+        result.keys.push(next);
+    }
+    if result.keys.is_empty() && !samples.is_empty() {
+        result.keys.push(key(0));
+    }
+    let keys_after = result.keys.len();
+    (result, ReduceStats { keys_before: samples.len(), keys_after, max_error })
+}
+
+/// 递归拆分拟合
+///
+/// * [fit] - 拟合 [from, to] 段, 返回段数据与 (最大误差, 最大误差处的采样序号)
+///
+/// 返回按顺序排列的各段与最大误差
+fn fit_segments<S, F: KeyFrameCurveScalar, E: Fn(usize, usize) -> (S, (F, usize))>(count: usize, tolerance: F, fit: E) -> (Vec<S>, F) {
+    let mut segments = Vec::new();
+    let mut max_error = F::zero();
+    if count < 2 {
+        return (segments, max_error);
+    }
+    // 待拟合的段, 后进先出, 先处理靠前的段
+    let mut pending = vec![(0, count - 1)];
+    while let Some((from, to)) = pending.pop() {
+        let (segment, (error, index)) = fit(from, to);
+        if error > tolerance && index > from && index < to {
+            pending.push((index, to));
+            pending.push((from, index));
+        } else {
+            max_error = max_error.max(error);
+            segments.push(segment);
+        }
+    }
+    (segments, max_error)
+}
+
+fn tolerance_value<T, F: KeyFrameCurveScalar, D: Fn(&T, &T) -> F>(samples: &[(FrameIndex, T)], tolerance: ReduceTolerance<F>, distance: &D) -> F {
+    let range = samples.iter().skip(1).fold(F::zero(), |range, (_, value)| range.max(distance(value, &samples[0].1)));
+    tolerance.absolute.max(tolerance.relative * range)
+}
+
+/// 采样在段内的进度
+fn amount<T, F: KeyFrameCurveScalar>(samples: &[(FrameIndex, T)], from: usize, to: usize, index: usize) -> F {
+    (F::from_frame_index(samples[index].0) - F::from_frame_index(samples[from].0)) / (F::from_frame_index(samples[to].0) - F::from_frame_index(samples[from].0))
+}
+
+/// Hermite 基函数 [h00, h01, h10, h11]
+fn hermite_basis<F: KeyFrameCurveScalar>(u: F) -> [F; 4] {
+    let (one, two) = (F::one(), F::one() + F::one());
+    let three = two + one;
+    let (squared, cubed) = (u * u, u * u * u);
+    [two * cubed - three * squared + one, -two * cubed + three * squared, cubed - two * squared + u, cubed - squared]
+}
+
+/// 固定首尾值, 最小二乘拟合首尾切线 (每秒); 中间采样不足时取弦的斜率
+fn fit_hermite<T: FrameDataValue<F>, F: KeyFrameCurveScalar>(samples: &[(FrameIndex, T)], from: usize, to: usize, fps: F) -> [T; 2] {
+    let (value1, value2) = (&samples[from].1, &samples[to].1);
+    let seconds = (F::from_frame_index(samples[to].0) - F::from_frame_index(samples[from].0)) / fps;
+    let zero = value1.append(value1, -F::one());
+    let chord = value2.append(value1, -F::one());
+    if seconds <= F::zero() {
+        return [zero.clone(), zero];
+    }
+
+    // 以 切线 * 时长 为未知量的正规方程
+    let (mut a00, mut a01, mut a11) = (F::zero(), F::zero(), F::zero());
+    let (mut r0, mut r1) = (zero.clone(), zero.clone());
+    for index in from + 1..to {
+        let [h00, h01, h10, h11] = hermite_basis::<F>(amount(samples, from, to, index));
+        let residual = samples[index].1.append(value1, -h00).append(value2, -h01);
+        a00 += h10 * h10;
+        a01 += h10 * h11;
+        a11 += h11 * h11;
+        r0 = r0.append(&residual, h10);
+        r1 = r1.append(&residual, h11);
+    }
+    let det = a00 * a11 - a01 * a01;
+    let (tangent1, tangent2) = if det <= F::from_f64_value(1e-9) * a00 * a11 || det <= F::zero() {
+        (chord.clone(), chord)
+    } else {
+        (zero.append(&r0, a11 / det).append(&r1, -a01 / det), zero.append(&r1, a00 / det).append(&r0, -a01 / det))
+    };
+    [zero.append(&tangent1, F::one() / seconds), zero.append(&tangent2, F::one() / seconds)]
+}
+
+/// Hermite 段的 (最大误差, 最大误差处的采样序号)
+fn hermite_error<T: FrameDataValue<F>, F: KeyFrameCurveScalar, D: Fn(&T, &T) -> F>(samples: &[(FrameIndex, T)], from: usize, to: usize, fps: F, tangents: &[T; 2], distance: &D) -> (F, usize) {
+    let seconds = (F::from_frame_index(samples[to].0) - F::from_frame_index(samples[from].0)) / fps;
+    (from + 1..to).fold((F::zero(), from), |(max, max_index), index| {
+        let value = T::hermite(&samples[from].1, &tangents[0], &samples[to].1, &tangents[1], amount(samples, from, to, index), seconds);
+        let error = distance(&samples[index].1, &value);
+        if error > max { (error, index) } else { (max, max_index) }
+    })
+}
+
+/// 保持切线方向, 最小二乘拟合 (时间, 值) 平面上的控制点距离, 返回 ((最大误差, 序号), [出权重, 入权重])
+fn fit_weights<F: KeyFrameCurveScalar>(samples: &[(FrameIndex, F)], from: usize, to: usize, fps: F, tangents: &[F; 2]) -> ((F, usize), [F; 2]) {
+    let third = F::one() / F::from_f64_value(3.);
+    let (value1, value2) = (samples[from].1, samples[to].1);
+    let seconds = (F::from_frame_index(samples[to].0) - F::from_frame_index(samples[from].0)) / fps;
+    let mut weights = [third, third];
+    if to <= from + 1 || seconds <= F::zero() {
+        return ((F::zero(), from), weights);
+    }
+    let (min, max) = (F::from_f64_value(MIN_WEIGHT), F::from_f64_value(MAX_WEIGHT));
+    // 切线方向 (时间归一化到 [0, 1])
+    let direction1 = [F::one(), tangents[0] * seconds];
+    let direction2 = [-F::one(), -tangents[1] * seconds];
+
+    for _ in 0..WEIGHT_ITERATIONS {
+        let (mut c00, mut c01, mut c11, mut x0, mut x1) = (F::zero(), F::zero(), F::zero(), F::zero(), F::zero());
+        for index in from + 1..to {
+            let u = amount(samples, from, to, index);
+            let s = bezier::cubic_bezier_solve(weights[0], F::one() - weights[1], u);
+            let [b0, b1, b2, b3] = bernstein(s);
+            let a1 = [direction1[0] * b1, direction1[1] * b1];
+            let a2 = [direction2[0] * b2, direction2[1] * b2];
+            let rest = [u - (b2 + b3), samples[index].1 - (value1 * (b0 + b1) + value2 * (b2 + b3))];
+            c00 += a1[0] * a1[0] + a1[1] * a1[1];
+            c01 += a1[0] * a2[0] + a1[1] * a2[1];
+            c11 += a2[0] * a2[0] + a2[1] * a2[1];
+            x0 += rest[0] * a1[0] + rest[1] * a1[1];
+            x1 += rest[0] * a2[0] + rest[1] * a2[1];
+        }
+        let det = c00 * c11 - c01 * c01;
+        if det <= F::from_f64_value(1e-12) {
+            break;
+        }
+        weights = [((c11 * x0 - c01 * x1) / det).clamp(min, max), ((c00 * x1 - c01 * x0) / det).clamp(min, max)];
+    }
+
+    let error = (from + 1..to).fold((F::zero(), from), |(max, max_index), index| {
+        let s = bezier::cubic_bezier_solve(weights[0], F::one() - weights[1], amount(samples, from, to, index));
+        let controls = [value1, value1 + tangents[0] * seconds * weights[0], value2 - tangents[1] * seconds * weights[1], value2];
+        let error = (samples[index].1 - bezier_value(controls, s)).abs();
+        if error > max { (error, index) } else { (max, max_index) }
+    });
+    (error, weights)
+}
+
+fn bernstein<F: KeyFrameCurveScalar>(s: F) -> [F; 4] {
+    let three = F::one() + F::one() + F::one();
+    let r = F::one() - s;
+    [r * r * r, three * r * r * s, three * r * s * s, s * s * s]
+}
+
+fn bezier_value<F: KeyFrameCurveScalar>(controls: [F; 4], s: F) -> F {
+    let [b0, b1, b2, b3] = bernstein(s);
+    controls[0] * b0 + controls[1] * b1 + controls[2] * b2 + controls[3] * b3
+}
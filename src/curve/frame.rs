@@ -1,23 +1,72 @@
 
-use std::ops::Add;
+use core::ops::Add;
 
 #[cfg(feature = "nalgebra")]
-use nalgebra::{Vector2, Vector3, Vector4};
+use nalgebra::{allocator::Allocator, DefaultAllocator, Dim, OMatrix, Point, SVector, Scalar, Quaternion, UnitQuaternion};
 
-use super::ErrorCurve;
+use crate::float::CurveFloat;
+
+use super::{ErrorCurve, FrameIndex};
 
 /// 关键帧曲线数值类型
 pub type KeyFrameCurveValue = f32;
 
+/// 曲线计算使用的标量类型 (f32 / f64)
+/// 默认为 f32 (KeyFrameCurveValue), 长时间轴需要亚帧精度时使用 f64
+pub trait KeyFrameCurveScalar: CurveFloat {
+    fn from_f64_value(value: f64) -> Self;
+    fn to_f64_value(self) -> f64;
+    fn from_frame_index(frame: FrameIndex) -> Self;
+    fn to_frame_index(self) -> FrameIndex;
+}
+
+impl KeyFrameCurveScalar for f32 {
+    #[inline]
+    fn from_f64_value(value: f64) -> Self {
+        value as f32
+    }
+    #[inline]
+    fn to_f64_value(self) -> f64 {
+        self as f64
+    }
+    #[inline]
+    fn from_frame_index(frame: FrameIndex) -> Self {
+        frame as f32
+    }
+    #[inline]
+    fn to_frame_index(self) -> FrameIndex {
+        self as FrameIndex
+    }
+}
+
+impl KeyFrameCurveScalar for f64 {
+    #[inline]
+    fn from_f64_value(value: f64) -> Self {
+        value
+    }
+    #[inline]
+    fn to_f64_value(self) -> f64 {
+        self
+    }
+    #[inline]
+    fn from_frame_index(frame: FrameIndex) -> Self {
+        frame as f64
+    }
+    #[inline]
+    fn to_frame_index(self) -> FrameIndex {
+        self as FrameIndex
+    }
+}
+
 /// 构建帧数据结构
 #[derive(Debug)]
-pub struct CurveFrameValue<T: FrameDataValue> {
+pub struct CurveFrameValue<T: Clone> {
     /// 帧数据值
     value: T,
     args: [T; 2]
 }
 
-impl<T: FrameDataValue> CurveFrameValue<T> {
+impl<T: Clone> CurveFrameValue<T> {
     pub fn new(value: T, args: [T; 2]) -> Self {
         CurveFrameValue {
             value,
@@ -35,8 +84,27 @@ impl<T: FrameDataValue> CurveFrameValue<T> {
     }
 }
 
-pub trait FrameValueScale {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self;
+pub trait FrameValueScale<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    fn scale(&self, rhs: F) -> Self;
+    /// 相对 reference 的差量, 默认 self - reference; 旋转类型为 self * reference⁻¹
+    fn difference(&self, reference: &Self) -> Self
+    where
+        Self: Sized + Clone + Add<Output = Self>,
+    {
+        self.clone() + reference.scale(-F::one())
+    }
+    /// 叠加差量, 默认 self + difference * amount; 旋转类型为 difference^amount * self
+    fn add_difference(&self, difference: &Self, amount: F) -> Self
+    where
+        Self: Sized + Clone + Add<Output = Self>,
+    {
+        self.clone() + difference.scale(amount)
+    }
+}
+
+/// 旋转类数据的球面插值
+pub trait FrameValueSlerp<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    fn slerp(&self, rhs: &Self, amount: F) -> Self;
 }
 
 pub type KeyFrameDataType = usize;
@@ -65,23 +133,39 @@ impl KeyFrameDataTypeAllocator {
     }
 }
 
+/// 帧数据大小 - 与计算标量类型无关, 单独定义以便 `u8::size()` 无需指定标量类型
+/// 不是 FrameDataValue 的前置约束; 通用实现只覆盖 `FrameValueScale<f32>`, 其余类型按需实现
+///
+/// 单位为字节: 标量为 `size_of`, 数组 / 元组 / 向量等复合类型为各分量之和 (不含对齐填充)
+pub trait FrameValueSize {
+    fn size() -> usize;
+}
+
 // pub trait FrameDataValue: Clone + Copy + FrameValueScale + FrameValueInterpolate + Add<Output = Self> {
 // }
-pub trait FrameDataValue: Clone {
-    fn interpolate(&self, rhs: &Self, amount: KeyFrameCurveValue) -> Self;
-    fn append(&self, rhs: &Self, amount: KeyFrameCurveValue) -> Self;
-    fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: KeyFrameCurveValue, frame_delta: KeyFrameCurveValue) -> Self;
-    fn size() -> usize;
+pub trait FrameDataValue<F: KeyFrameCurveScalar = KeyFrameCurveValue>: Clone {
+    fn interpolate(&self, rhs: &Self, amount: F) -> Self;
+    fn append(&self, rhs: &Self, amount: F) -> Self;
+    fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: F, frame_delta: F) -> Self;
+    /// 相对 reference 的差量 - 叠加动画的差量姿态与曲线切线使用
+    fn delta(&self, reference: &Self) -> Self {
+        self.append(reference, -F::one())
+    }
+    /// 叠加差量 delta * amount
+    fn add_delta(&self, delta: &Self, amount: F) -> Self {
+        self.append(delta, amount)
+    }
 }
 
-impl<T: Clone + FrameValueScale + Add<Output = Self>> FrameDataValue for T {
-    fn interpolate(&self, rhs: &Self, amount: KeyFrameCurveValue) -> Self {
-        self.scale(1.0 - amount) + rhs.scale(amount)
+/// 对任意标量类型实现, 只实现 `FrameValueScale<f64>` 的类型同样可用于 f64 曲线
+impl<F: KeyFrameCurveScalar, T: Clone + FrameValueScale<F> + Add<Output = Self>> FrameDataValue<F> for T {
+    fn interpolate(&self, rhs: &Self, amount: F) -> Self {
+        self.scale(F::one() - amount) + rhs.scale(amount)
     }
-    fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: KeyFrameCurveValue, frame_delta: KeyFrameCurveValue) -> Self {
-        let _1 = 1 as KeyFrameCurveValue;
-        let _2 = 2 as KeyFrameCurveValue;
-        let _3 = 3 as KeyFrameCurveValue;
+    fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: F, frame_delta: F) -> Self {
+        let _1 = F::one();
+        let _2 = _1 + _1;
+        let _3 = _2 + _1;
 
         let squared = amount * amount;
         let cubed = amount * squared;
@@ -92,83 +176,386 @@ impl<T: Clone + FrameValueScale + Add<Output = Self>> FrameDataValue for T {
 
         return (((value1.scale(part1)) + (value2.scale(part2))) + (tangent1.scale(part3 * frame_delta))) + (tangent2.scale(part4 * frame_delta));
     }
-    fn append(&self, rhs: &Self, amount: KeyFrameCurveValue) -> Self {
+    fn append(&self, rhs: &Self, amount: F) -> Self {
         self.clone() + rhs.scale(amount)
     }
+    fn delta(&self, reference: &Self) -> Self {
+        self.difference(reference)
+    }
+    fn add_delta(&self, delta: &Self, amount: F) -> Self {
+        self.add_difference(delta, amount)
+    }
+}
+
+impl<T: FrameValueScale<KeyFrameCurveValue>> FrameValueSize for T {
     fn size() -> usize {
-        8
+        core::mem::size_of::<T>()
     }
 }
 
 /// f32
-impl FrameValueScale for f32 {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
+impl FrameValueScale<f32> for f32 {
+    fn scale(&self, rhs: f32) -> Self {
         self * rhs
     }
 }
 
 /// f64
-impl FrameValueScale for f64 {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
+impl FrameValueScale<f32> for f64 {
+    fn scale(&self, rhs: f32) -> Self {
         self * rhs as f64
     }
 }
 
-/// u8
-impl FrameValueScale for u8 {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (*self as KeyFrameCurveValue * rhs) as u8
+/// f64
+impl FrameValueScale<f64> for f64 {
+    fn scale(&self, rhs: f64) -> Self {
+        self * rhs
     }
 }
 
-/// u16
-impl FrameValueScale for u16 {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (*self as KeyFrameCurveValue * rhs) as u16
+/// 整数类型
+/// 以浮点计算插值结果, 最后统一四舍五入并饱和到类型取值范围, 避免逐项截断与溢出回绕
+/// 64 位类型超出 f64 精确范围 (2^53), interpolate / append 改以 i128 精确计算;
+/// hermite 以起始值为基准计算增量, 仅增量与切线超过 2^53 时损失精度
+///
+/// 无符号类型的差量以补码表示负数, `add_delta` 按有符号数解释
+///
+/// 整数类型不再实现 FrameValueScale - 其 FrameDataValue 需单独实现以四舍五入和饱和,
+/// 与 FrameValueScale 的通用实现冲突; 缩放可使用 `append`
+macro_rules! impl_frame_data_value_integer {
+    ($($ty:ty => $signed:ty),*) => {
+        $(
+            impl_frame_data_value_integer!(@scalar $ty, $signed, f32);
+            impl_frame_data_value_integer!(@scalar $ty, $signed, f64);
+
+            impl FrameValueSize for $ty {
+                fn size() -> usize {
+                    core::mem::size_of::<$ty>()
+                }
+            }
+        )*
+    };
+    (@scalar $ty:ty, $signed:ty, $f:ty) => {
+        impl FrameDataValue<$f> for $ty {
+            fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                let amount = amount as f64;
+                let result = *self as f64 * (1.0 - amount) + *rhs as f64 * amount;
+                libm::round(result) as $ty
+            }
+            fn append(&self, rhs: &Self, amount: $f) -> Self {
+                let result = *self as f64 + *rhs as f64 * amount as f64;
+                libm::round(result) as $ty
+            }
+            fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                let result = hermite_integer_delta(*value1 as f64, *tangent1 as f64, *value2 as f64, *tangent2 as f64, amount as f64, frame_delta as f64);
+                libm::round(result) as $ty
+            }
+            fn delta(&self, reference: &Self) -> Self {
+                self.wrapping_sub(*reference)
+            }
+            fn add_delta(&self, delta: &Self, amount: $f) -> Self {
+                let delta = unwrap_integer_delta(*self as i128, (*delta as $signed) as i128, <$ty>::BITS, <$ty>::MIN as i128, <$ty>::MAX as i128);
+                let result = *self as f64 + delta as f64 * amount as f64;
+                libm::round(result) as $ty
+            }
+        }
+    };
+}
+
+/// 64 位整数类型 - 以 i128 精确计算
+macro_rules! impl_frame_data_value_integer_wide {
+    ($($ty:ty => $signed:ty),*) => {
+        $(
+            impl_frame_data_value_integer_wide!(@scalar $ty, $signed, f32);
+            impl_frame_data_value_integer_wide!(@scalar $ty, $signed, f64);
+
+            impl FrameValueSize for $ty {
+                fn size() -> usize {
+                    core::mem::size_of::<$ty>()
+                }
+            }
+        )*
+    };
+    (@scalar $ty:ty, $signed:ty, $f:ty) => {
+        impl FrameDataValue<$f> for $ty {
+            fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                let delta = mul_round_i128(*rhs as i128 - *self as i128, amount as f64);
+                (*self as i128).saturating_add(delta).clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty
+            }
+            fn append(&self, rhs: &Self, amount: $f) -> Self {
+                let delta = mul_round_i128(*rhs as i128, amount as f64);
+                (*self as i128).saturating_add(delta).clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty
+            }
+            fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                let delta = (*value2 as i128 - *value1 as i128) as f64;
+                let result = hermite_integer_delta(0.0, *tangent1 as f64, delta, *tangent2 as f64, amount as f64, frame_delta as f64);
+                (*value1 as i128).saturating_add(libm::round(result) as i128).clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty
+            }
+            fn delta(&self, reference: &Self) -> Self {
+                self.wrapping_sub(*reference)
+            }
+            fn add_delta(&self, delta: &Self, amount: $f) -> Self {
+                let delta = unwrap_integer_delta(*self as i128, (*delta as $signed) as i128, <$ty>::BITS, <$ty>::MIN as i128, <$ty>::MAX as i128);
+                let delta = mul_round_i128(delta, amount as f64);
+                (*self as i128).saturating_add(delta).clamp(<$ty>::MIN as i128, <$ty>::MAX as i128) as $ty
+            }
+        }
+    };
+}
+
+/// 整数差值以补码回绕形式保存 (delta = wrapping_sub), 还原为真实差值:
+/// 在 delta 与 delta ± 2^bits 中选取使 base + delta 落在类型范围内的一个
+/// 以 reference 为 base 时还原结果精确, 与 amount = 1 的回加互逆
+fn unwrap_integer_delta(base: i128, delta: i128, bits: u32, min: i128, max: i128) -> i128 {
+    let modulus = 1i128 << bits;
+    if base + delta < min {
+        delta + modulus
+    } else if base + delta > max {
+        delta - modulus
+    } else {
+        delta
+    }
+}
+
+fn hermite_integer_delta(value1: f64, tangent1: f64, value2: f64, tangent2: f64, amount: f64, frame_delta: f64) -> f64 {
+    let squared = amount * amount;
+    let cubed = amount * squared;
+    let part1 = ((2.0 * cubed) - (3.0 * squared)) + 1.0;
+    let part2 = (-2.0 * cubed) + (3.0 * squared);
+    let part3 = (cubed - (2.0 * squared)) + amount;
+    let part4 = cubed - squared;
+
+    value1 * part1 + value2 * part2 + tangent1 * (part3 * frame_delta) + tangent2 * (part4 * frame_delta)
+}
+
+/// value * amount 四舍五入 (远离 0), 将 amount 拆为 尾数 * 2^指数 精确计算
+/// |value| 不超过 2^65, 与 53 位尾数的乘积不会溢出 i128
+fn mul_round_i128(value: i128, amount: f64) -> i128 {
+    if value == 0 || amount == 0.0 || amount.is_nan() {
+        return 0;
+    }
+    let negative = (value < 0) != amount.is_sign_negative();
+    let bits = amount.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1u64 << 52) - 1);
+    let (mantissa, shift) = if exponent == 0 { (fraction, 1 - 1075) } else { (fraction | (1u64 << 52), exponent - 1075) };
+    let product = value.unsigned_abs() * mantissa as u128;
+    let max = i128::MAX as u128;
+    let magnitude = if exponent == 0x7ff || (shift >= 0 && product > max >> shift.min(127)) {
+        // 无穷大或超出 i128 范围, 饱和
+        max
+    } else if shift >= 0 {
+        product << shift
+    } else if -shift >= 120 {
+        0
+    } else {
+        let shift = -shift as u32;
+        (product + (1u128 << (shift - 1))) >> shift
+    };
+    let magnitude = magnitude as i128;
+    if negative { -magnitude } else { magnitude }
+}
+
+impl_frame_data_value_integer!(u8 => i8, u16 => i16, u32 => i32, i8 => i8, i16 => i16, i32 => i32);
+impl_frame_data_value_integer_wide!(u64 => i64, usize => isize, i64 => i64, isize => isize);
+
+/// 定长数组 - 逐元素计算
+macro_rules! impl_frame_data_value_array {
+    ($($f:ty),*) => {
+        $(
+            impl<T: FrameDataValue<$f>, const N: usize> FrameDataValue<$f> for [T; N] {
+                fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                    core::array::from_fn(|i| self[i].interpolate(&rhs[i], amount))
+                }
+                fn append(&self, rhs: &Self, amount: $f) -> Self {
+                    core::array::from_fn(|i| self[i].append(&rhs[i], amount))
+                }
+                fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                    core::array::from_fn(|i| T::hermite(&value1[i], &tangent1[i], &value2[i], &tangent2[i], amount, frame_delta))
+                }
+                fn delta(&self, reference: &Self) -> Self {
+                    core::array::from_fn(|i| self[i].delta(&reference[i]))
+                }
+                fn add_delta(&self, delta: &Self, amount: $f) -> Self {
+                    core::array::from_fn(|i| self[i].add_delta(&delta[i], amount))
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_data_value_array!(f32, f64);
+
+impl<T: FrameValueSize, const N: usize> FrameValueSize for [T; N] {
+    fn size() -> usize {
+        T::size() * N
     }
 }
 
-/// u32
-impl FrameValueScale for u32 {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (*self as KeyFrameCurveValue * rhs) as u32
+/// 元组 - 逐元素计算
+macro_rules! impl_frame_data_value_tuple {
+    ($f:ty; $(($($name:ident : $idx:tt),+)),*) => {
+        $(
+            impl<$($name: FrameDataValue<$f>),+> FrameDataValue<$f> for ($($name,)+) {
+                fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                    ($(self.$idx.interpolate(&rhs.$idx, amount),)+)
+                }
+                fn append(&self, rhs: &Self, amount: $f) -> Self {
+                    ($(self.$idx.append(&rhs.$idx, amount),)+)
+                }
+                fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                    ($($name::hermite(&value1.$idx, &tangent1.$idx, &value2.$idx, &tangent2.$idx, amount, frame_delta),)+)
+                }
+                fn delta(&self, reference: &Self) -> Self {
+                    ($(self.$idx.delta(&reference.$idx),)+)
+                }
+                fn add_delta(&self, delta: &Self, amount: $f) -> Self {
+                    ($(self.$idx.add_delta(&delta.$idx, amount),)+)
+                }
+            }
+        )*
+    };
+    (@size $(($($name:ident),+)),*) => {
+        $(
+            impl<$($name: FrameValueSize),+> FrameValueSize for ($($name,)+) {
+                fn size() -> usize {
+                    0 $(+ $name::size())+
+                }
+            }
+        )*
+    };
+    ($($f:ty),*) => {
+        $(
+            impl_frame_data_value_tuple!(
+                $f;
+                (A: 0),
+                (A: 0, B: 1),
+                (A: 0, B: 1, C: 2),
+                (A: 0, B: 1, C: 2, D: 3),
+                (A: 0, B: 1, C: 2, D: 3, E: 4),
+                (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5),
+                (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6),
+                (A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7)
+            );
+        )*
+    };
+}
+
+impl_frame_data_value_tuple!(f32, f64);
+impl_frame_data_value_tuple!(
+    @size
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H)
+);
+
+#[cfg(feature = "nalgebra")]
+/// Matrix - 任意维度 (Vector2/3/4, SVector, SMatrix, DVector, DMatrix ...)
+impl<R: Dim, C: Dim> FrameValueScale<f32> for OMatrix<f32, R, C>
+where
+    DefaultAllocator: Allocator<f32, R, C>,
+{
+    fn scale(&self, rhs: f32) -> Self {
+        self * rhs
     }
 }
 
-/// u64
-impl FrameValueScale for u64 {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (*self as KeyFrameCurveValue * rhs) as u64
+#[cfg(feature = "nalgebra")]
+/// Matrix f64 - 任意维度
+impl<R: Dim, C: Dim> FrameValueScale<f32> for OMatrix<f64, R, C>
+where
+    DefaultAllocator: Allocator<f64, R, C>,
+{
+    fn scale(&self, rhs: f32) -> Self {
+        self * rhs as f64
     }
 }
 
-/// usize
-impl FrameValueScale for usize {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (*self as KeyFrameCurveValue * rhs) as usize
+#[cfg(feature = "nalgebra")]
+/// Matrix f64 - 任意维度
+impl<R: Dim, C: Dim> FrameValueScale<f64> for OMatrix<f64, R, C>
+where
+    DefaultAllocator: Allocator<f64, R, C>,
+{
+    fn scale(&self, rhs: f64) -> Self {
+        self * rhs
     }
 }
 
+/// Point - 任意维度, 以坐标向量计算
+#[cfg(feature = "nalgebra")]
+macro_rules! impl_frame_data_value_point {
+    ($($f:ty),*) => {
+        $(
+            impl<N: Scalar, const D: usize> FrameDataValue<$f> for Point<N, D>
+            where
+                SVector<N, D>: FrameDataValue<$f>,
+            {
+                fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                    Point::from(self.coords.interpolate(&rhs.coords, amount))
+                }
+                fn append(&self, rhs: &Self, amount: $f) -> Self {
+                    Point::from(self.coords.append(&rhs.coords, amount))
+                }
+                fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                    Point::from(SVector::hermite(&value1.coords, &tangent1.coords, &value2.coords, &tangent2.coords, amount, frame_delta))
+                }
+                fn delta(&self, reference: &Self) -> Self {
+                    Point::from(self.coords.delta(&reference.coords))
+                }
+                fn add_delta(&self, delta: &Self, amount: $f) -> Self {
+                    Point::from(self.coords.add_delta(&delta.coords, amount))
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "nalgebra")]
+impl_frame_data_value_point!(f32, f64);
+
 #[cfg(feature = "nalgebra")]
-/// Vector2
-impl FrameValueScale for Vector2<f32> {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (self * rhs) as Vector2<f32>
+impl<N: Scalar, const D: usize> FrameValueSize for Point<N, D>
+where
+    SVector<N, D>: FrameValueSize,
+{
+    fn size() -> usize {
+        <SVector<N, D> as FrameValueSize>::size()
     }
 }
 
 #[cfg(feature = "nalgebra")]
-/// Vector3
-impl FrameValueScale for Vector3<f32> {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (self * rhs) as Vector3<f32>
+/// Quaternion
+impl FrameValueScale<f32> for Quaternion<f32> {
+    fn scale(&self, rhs: f32) -> Self {
+        self * rhs
+    }
+    /// 旋转差量 self * reference⁻¹, reference 不可逆时取 self
+    fn difference(&self, reference: &Self) -> Self {
+        self * reference.try_inverse().unwrap_or_else(Quaternion::identity)
+    }
+    /// 差量旋转按 amount 缩放角度后左乘
+    fn add_difference(&self, difference: &Self, amount: f32) -> Self {
+        UnitQuaternion::new_normalize(*difference).powf(amount).into_inner() * self
     }
 }
 
 #[cfg(feature = "nalgebra")]
-/// Vector4
-impl FrameValueScale for Vector4<f32> {
-    fn scale(&self, rhs: KeyFrameCurveValue) -> Self {
-        (self * rhs) as Vector4<f32>
+/// Quaternion - 归一化后球面插值
+impl FrameValueSlerp<f32> for Quaternion<f32> {
+    fn slerp(&self, rhs: &Self, amount: f32) -> Self {
+        let from = UnitQuaternion::new_normalize(*self);
+        let to = UnitQuaternion::new_normalize(*rhs);
+        // 相差 180 度时球面插值无定义, 退化为归一化线性插值
+        match from.try_slerp(&to, amount, f32::EPSILON) {
+            Some(result) => result.into_inner(),
+            None => from.nlerp(&to, amount).into_inner(),
+        }
     }
-}
\ No newline at end of file
+}
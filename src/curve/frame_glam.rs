@@ -0,0 +1,140 @@
+//! glam 类型的关键帧数据实现
+
+use glam::{DQuat, DVec2, DVec3, DVec4, Quat, Vec2, Vec3, Vec3A, Vec4};
+
+use super::{binary::FrameValueBytes, compress::{FrameValueComponents, FrameValueQuaternion}, frame::{FrameValueScale, FrameValueSlerp}};
+
+macro_rules! impl_frame_value_scale_glam {
+    ($($ty:ty : $f:ty => $target:ty),*) => {
+        $(
+            /// glam
+            impl FrameValueScale<$f> for $ty {
+                fn scale(&self, rhs: $f) -> Self {
+                    *self * rhs as $target
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_scale_glam!(
+    Vec2: f32 => f32,
+    Vec3: f32 => f32,
+    Vec3A: f32 => f32,
+    Vec4: f32 => f32,
+    DVec2: f32 => f64,
+    DVec3: f32 => f64,
+    DVec4: f32 => f64,
+    DVec2: f64 => f64,
+    DVec3: f64 => f64,
+    DVec4: f64 => f64
+);
+
+macro_rules! impl_frame_value_scale_glam_quat {
+    ($($ty:ty : $f:ty => $target:ty),*) => {
+        $(
+            /// glam - 差量为旋转差量 self * reference⁻¹
+            impl FrameValueScale<$f> for $ty {
+                fn scale(&self, rhs: $f) -> Self {
+                    *self * rhs as $target
+                }
+                fn difference(&self, reference: &Self) -> Self {
+                    *self * (reference.conjugate() / reference.length_squared())
+                }
+                fn add_difference(&self, difference: &Self, amount: $f) -> Self {
+                    <$ty>::IDENTITY.slerp(difference.normalize(), amount as $target) * *self
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_scale_glam_quat!(
+    Quat: f32 => f32,
+    DQuat: f32 => f64,
+    DQuat: f64 => f64
+);
+
+/// Quat - 归一化后球面插值
+impl FrameValueSlerp<f32> for Quat {
+    fn slerp(&self, rhs: &Self, amount: f32) -> Self {
+        self.normalize().slerp(rhs.normalize(), amount)
+    }
+}
+
+/// DQuat - 归一化后球面插值
+impl FrameValueSlerp<f64> for DQuat {
+    fn slerp(&self, rhs: &Self, amount: f64) -> Self {
+        self.normalize().slerp(rhs.normalize(), amount)
+    }
+}
+
+macro_rules! impl_frame_value_components_glam {
+    ($($ty:ty : $scalar:ty, $dimension:expr);*) => {
+        $(
+            /// glam
+            impl FrameValueComponents for $ty {
+                fn dimension() -> usize {
+                    $dimension
+                }
+                fn write_components(&self, components: &mut [f32]) {
+                    components.iter_mut().zip(self.to_array().iter()).for_each(|(component, value)| *component = *value as f32);
+                }
+                fn from_components(components: &[f32]) -> Self {
+                    <$ty>::from_array(core::array::from_fn(|index| components[index] as $scalar))
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_components_glam!(
+    Vec2: f32, 2;
+    Vec3: f32, 3;
+    Vec3A: f32, 3;
+    Vec4: f32, 4;
+    DVec2: f64, 2;
+    DVec3: f64, 3;
+    DVec4: f64, 4
+);
+
+/// Quat
+impl FrameValueQuaternion for Quat {
+    fn to_xyzw(&self) -> [f32; 4] {
+        self.to_array()
+    }
+    fn from_xyzw(xyzw: [f32; 4]) -> Self {
+        Quat::from_array(xyzw)
+    }
+}
+
+macro_rules! impl_frame_value_bytes_glam {
+    ($($ty:ty : $scalar:ty, $dimension:expr);*) => {
+        $(
+            /// glam
+            impl FrameValueBytes for $ty {
+                fn byte_size() -> usize {
+                    core::mem::size_of::<$scalar>() * $dimension
+                }
+                fn write_bytes(&self, bytes: &mut alloc::vec::Vec<u8>) {
+                    self.to_array().write_bytes(bytes);
+                }
+                fn read_bytes(bytes: &[u8]) -> Self {
+                    <$ty>::from_array(<[$scalar; $dimension]>::read_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_bytes_glam!(
+    Vec2: f32, 2;
+    Vec3: f32, 3;
+    Vec3A: f32, 3;
+    Vec4: f32, 4;
+    Quat: f32, 4;
+    DVec2: f64, 2;
+    DVec3: f64, 3;
+    DVec4: f64, 4;
+    DQuat: f64, 4
+);
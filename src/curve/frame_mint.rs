@@ -0,0 +1,127 @@
+//! mint 类型的关键帧数据实现
+//!
+//! mint 类型不提供运算, 逐分量委托给分量类型计算
+
+use mint::{Point2, Point3, Quaternion, Vector2, Vector3, Vector4};
+
+use crate::float::CurveFloat;
+
+use super::frame::{FrameDataValue, FrameValueSize, FrameValueSlerp};
+
+macro_rules! impl_frame_data_value_mint {
+    ($f:ty; $($ty:ident[$n:expr] { $($field:ident),+ }),*) => {
+        $(
+            impl<T: FrameDataValue<$f>> FrameDataValue<$f> for $ty<T> {
+                fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                    $ty { $($field: self.$field.interpolate(&rhs.$field, amount)),+ }
+                }
+                fn append(&self, rhs: &Self, amount: $f) -> Self {
+                    $ty { $($field: self.$field.append(&rhs.$field, amount)),+ }
+                }
+                fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                    $ty { $($field: T::hermite(&value1.$field, &tangent1.$field, &value2.$field, &tangent2.$field, amount, frame_delta)),+ }
+                }
+                fn delta(&self, reference: &Self) -> Self {
+                    $ty { $($field: self.$field.delta(&reference.$field)),+ }
+                }
+                fn add_delta(&self, delta: &Self, amount: $f) -> Self {
+                    $ty { $($field: self.$field.add_delta(&delta.$field, amount)),+ }
+                }
+            }
+        )*
+    };
+    ($($f:ty),*) => {
+        $(
+            impl_frame_data_value_mint!(
+                $f;
+                Vector2[2] { x, y },
+                Vector3[3] { x, y, z },
+                Vector4[4] { x, y, z, w },
+                Point2[2] { x, y },
+                Point3[3] { x, y, z }
+            );
+
+            /// Quaternion - 逐分量线性插值, 旋转插值使用 FrameValueSlerp
+            /// 分量类型不限, 差量同样逐分量计算; 旋转差量需转换为 nalgebra / glam 四元数
+            impl<T: FrameDataValue<$f>> FrameDataValue<$f> for Quaternion<T> {
+                fn interpolate(&self, rhs: &Self, amount: $f) -> Self {
+                    Quaternion { v: self.v.interpolate(&rhs.v, amount), s: self.s.interpolate(&rhs.s, amount) }
+                }
+                fn append(&self, rhs: &Self, amount: $f) -> Self {
+                    Quaternion { v: self.v.append(&rhs.v, amount), s: self.s.append(&rhs.s, amount) }
+                }
+                fn hermite(value1: &Self, tangent1: &Self, value2: &Self, tangent2: &Self, amount: $f, frame_delta: $f) -> Self {
+                    Quaternion {
+                        v: Vector3::hermite(&value1.v, &tangent1.v, &value2.v, &tangent2.v, amount, frame_delta),
+                        s: T::hermite(&value1.s, &tangent1.s, &value2.s, &tangent2.s, amount, frame_delta),
+                    }
+                }
+            }
+
+            /// Quaternion - 归一化后球面插值
+            impl FrameValueSlerp<$f> for Quaternion<$f> {
+                fn slerp(&self, rhs: &Self, amount: $f) -> Self {
+                    let [x, y, z, w] = quaternion_slerp(
+                        [self.v.x, self.v.y, self.v.z, self.s],
+                        [rhs.v.x, rhs.v.y, rhs.v.z, rhs.s],
+                        amount,
+                    );
+                    Quaternion { v: Vector3 { x, y, z }, s: w }
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_data_value_mint!(f32, f64);
+
+macro_rules! impl_frame_value_size_mint {
+    ($($ty:ident[$n:expr]),*) => {
+        $(
+            impl<T: FrameValueSize> FrameValueSize for $ty<T> {
+                fn size() -> usize {
+                    T::size() * $n
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_value_size_mint!(Vector2[2], Vector3[3], Vector4[4], Point2[2], Point3[3], Quaternion[4]);
+
+/// 四元数球面插值, 取最短路径; 夹角很小时退化为归一化线性插值
+fn quaternion_slerp<F: CurveFloat>(from: [F; 4], to: [F; 4], amount: F) -> [F; 4] {
+    let from = quaternion_normalize(from);
+    let mut to = quaternion_normalize(to);
+
+    let mut dot = F::zero();
+    for i in 0..4 {
+        dot += from[i] * to[i];
+    }
+    if dot < F::zero() {
+        to = to.map(|v| -v);
+        dot = -dot;
+    }
+
+    let one = F::one();
+    let (weight_from, weight_to) = if dot > F::from_f32(0.9995).unwrap() {
+        (one - amount, amount)
+    } else {
+        let theta = dot.min(one).acos();
+        let sin = theta.sin();
+        (((one - amount) * theta).sin() / sin, (amount * theta).sin() / sin)
+    };
+
+    quaternion_normalize(core::array::from_fn(|i| from[i] * weight_from + to[i] * weight_to))
+}
+
+fn quaternion_normalize<F: CurveFloat>(value: [F; 4]) -> [F; 4] {
+    let mut length = F::zero();
+    for v in value {
+        length += v * v;
+    }
+    match length.try_sqrt() {
+        Some(length) if length > F::zero() => value.map(|v| v / length),
+        _ => value,
+    }
+}
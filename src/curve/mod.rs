@@ -1,6 +1,11 @@
 //!
 //! 关键帧数据结构
 
+use alloc::vec::Vec;
+
+/// 关键帧序号, 单条曲线最多 65535 帧: 60 帧每秒约 18.2 分钟, 30 帧每秒约 36.4 分钟
+/// 更长的时间轴 (如过场动画) 可降低设计帧率, 或拆分为多段曲线由 `Timeline` 依次播放;
+/// f64 标量保证的是该范围内采样时间的精度
 pub type FrameIndex = u16;
 pub type FramePerSecond = u16;
 
@@ -10,11 +15,47 @@ pub type CubicBezier<T> = Vec<T>;
 #[derive(Debug)]
 pub enum ErrorCurve {
     KeyFrameDataTypeCannotAllocMore,
+    /// 同一数值类型 ID 绑定了不同的数值类型
+    KeyFrameDataTypeMismatch,
+    /// 曲线设计帧率与剪辑不一致
+    FramePerSecondMismatch,
+    /// 目标路径 + 属性 已存在曲线
+    ChannelAlreadyExists,
+    /// 通道数量与曲线组不一致
+    ChannelCountMismatch,
+    /// 曲线的关键帧时间或类型不一致
+    KeyFramesMismatch,
+    /// 没有关键帧, 无法计算
+    KeyFramesEmpty,
+    /// 帧序号超出 FrameIndex 范围
+    FrameIndexOverflow,
+    /// 二进制数据标识不符
+    BinaryInvalidHeader,
+    /// 不支持的二进制格式版本
+    BinaryUnsupportedVersion,
+    /// 二进制数据的 标量 / 数值 字节数与读取类型不符
+    BinaryValueTypeMismatch,
+    /// 二进制数据长度不足
+    BinaryUnexpectedEnd,
+    /// 二进制数据损坏
+    BinaryInvalidData,
 }
 
 pub mod frame;
 pub mod frame_curve;
 pub mod curves;
+pub mod bake;
+pub mod reduce;
+pub mod fit;
+pub mod compress;
+pub mod binary;
+pub mod bank;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+#[cfg(feature = "glam")]
+pub mod frame_glam;
+#[cfg(feature = "mint")]
+pub mod frame_mint;
 
 
 pub trait Repeat<N> {
@@ -0,0 +1,55 @@
+//!
+//! 曲线批量并行计算 (rayon)
+//! 每个结果只写入 output 中对应序号的位置, 与线程调度无关, 结果与串行计算一致
+
+use rayon::prelude::*;
+
+use crate::amount::AnimationAmountCalc;
+
+use super::{frame::{FrameDataValue, KeyFrameCurveScalar}, frame_curve::FrameCurve, ErrorCurve};
+
+/// 每个任务至少计算的数量, 避免任务过小时调度开销超过计算本身
+pub const PAR_MIN_LEN: usize = 1024;
+
+/// 在同一时间 (秒) 并行计算一组曲线, output[i] 为 curves[i] 的结果
+pub fn par_interple<T, F>(curves: &[FrameCurve<T, F>], time: F, amountcalc: &AnimationAmountCalc<F>, output: &mut [T]) -> Result<(), ErrorCurve>
+where
+    T: FrameDataValue<F> + Send + Sync,
+    F: KeyFrameCurveScalar,
+{
+    if curves.len() != output.len() {
+        return Err(ErrorCurve::ChannelCountMismatch);
+    }
+    output.par_iter_mut().with_min_len(PAR_MIN_LEN).zip(curves.par_iter()).for_each(|(result, curve)| {
+        *result = curve.interple(time, amountcalc);
+    });
+    Ok(())
+}
+
+/// 并行计算一组曲线各自的时间 (秒), output[i] 为 curves[i] 在 times[i] 的结果
+pub fn par_interple_times<T, F>(curves: &[FrameCurve<T, F>], times: &[F], amountcalc: &AnimationAmountCalc<F>, output: &mut [T]) -> Result<(), ErrorCurve>
+where
+    T: FrameDataValue<F> + Send + Sync,
+    F: KeyFrameCurveScalar,
+{
+    if curves.len() != output.len() || times.len() != output.len() {
+        return Err(ErrorCurve::ChannelCountMismatch);
+    }
+    output.par_iter_mut().with_min_len(PAR_MIN_LEN).zip(curves.par_iter().zip(times.par_iter())).for_each(|(result, (curve, time))| {
+        *result = curve.interple(*time, amountcalc);
+    });
+    Ok(())
+}
+
+impl<T: FrameDataValue<F> + Send + Sync, F: KeyFrameCurveScalar> FrameCurve<T, F> {
+    /// 并行计算曲线在一组时间 (秒) 的值, output[i] 为 times[i] 的结果
+    pub fn par_interple_many(&self, times: &[F], amountcalc: &AnimationAmountCalc<F>, output: &mut [T]) -> Result<(), ErrorCurve> {
+        if times.len() != output.len() {
+            return Err(ErrorCurve::ChannelCountMismatch);
+        }
+        output.par_iter_mut().with_min_len(PAR_MIN_LEN).zip(times.par_iter()).for_each(|(result, time)| {
+            *result = self.interple(*time, amountcalc);
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+//!
+//! 关键帧精简 - 在误差范围内移除冗余关键帧
+
+use alloc::vec::Vec;
+
+use crate::hermite;
+
+use super::{frame::{FrameDataValue, KeyFrameCurveScalar}, frame_curve::FrameCurve};
+
+/// 允许的误差 - 取 绝对误差 与 相对误差 * 数值范围 中的较大者
+/// 数值范围为各关键帧与首帧距离的最大值
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReduceTolerance<F: KeyFrameCurveScalar> {
+    pub absolute: F,
+    pub relative: F,
+}
+
+impl<F: KeyFrameCurveScalar> ReduceTolerance<F> {
+    pub fn absolute(absolute: F) -> Self {
+        Self { absolute, relative: F::zero() }
+    }
+    pub fn relative(relative: F) -> Self {
+        Self { absolute: F::zero(), relative }
+    }
+}
+
+/// 精简结果统计
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReduceStats<F: KeyFrameCurveScalar> {
+    pub keys_before: usize,
+    pub keys_after: usize,
+    /// 源关键帧处的最大误差
+    pub max_error: F,
+}
+
+impl<F: KeyFrameCurveScalar> ReduceStats<F> {
+    /// 压缩比 - 精简前后关键帧数之比
+    pub fn compression(&self) -> F {
+        if self.keys_after == 0 {
+            return F::one();
+        }
+        F::from_f64_value(self.keys_before as f64 / self.keys_after as f64)
+    }
+}
+
+impl<T: FrameDataValue<F>, F: KeyFrameCurveScalar> FrameCurve<T, F> {
+    /// 精简 FrameValues 曲线 - 移除可由前后关键帧线性插值得到的关键帧
+    ///
+    /// * [tolerance] - 允许的误差
+    /// * [distance] - 两个值之间的距离, 如 `|a, b| (a - b).abs()`
+    ///
+    pub fn reduce_frame_values<D: Fn(&T, &T) -> F>(&self, tolerance: ReduceTolerance<F>, distance: D) -> (FrameCurve<T, F>, ReduceStats<F>) {
+        let tolerance = self.tolerance_value(tolerance, &distance);
+        let (keys, max_error) = reduce_keys(self.key_count(), tolerance, |from, to| {
+            let (frame_from, frame_to) = (F::from_frame_index(self.frames[from]), F::from_frame_index(self.frames[to]));
+            (from + 1..to).fold(F::zero(), |error, index| {
+                let amount = (F::from_frame_index(self.frames[index]) - frame_from) / (frame_to - frame_from);
+                error.max(distance(&self.values[index], &self.values[from].interpolate(&self.values[to], amount)))
+            })
+        });
+
+        let mut result = FrameCurve::curve_frame_values(self.design_frame_per_second);
+        for &index in keys.iter() {
+            result.curve_frame_values_frame(self.frames[index], self.values[index].clone());
+        }
+        (result, self.reduce_stats(&keys, max_error))
+    }
+
+    /// 精简 FrameValues 曲线为 GLTFCubicSpline 曲线 - 切线取源曲线在关键帧处的斜率
+    ///
+    /// * [tolerance] - 允许的误差
+    /// * [distance] - 两个值之间的距离
+    ///
+    pub fn reduce_cubic_spline<D: Fn(&T, &T) -> F>(&self, tolerance: ReduceTolerance<F>, distance: D) -> (FrameCurve<T, F>, ReduceStats<F>) {
+        let tolerance = self.tolerance_value(tolerance, &distance);
+        let fps = F::from_frame_index(self.design_frame_per_second);
+        let slopes: Vec<T> = (0..self.key_count()).map(|index| self.slope(index)).collect();
+        let (keys, max_error) = reduce_keys(self.key_count(), tolerance, |from, to| {
+            let (frame_from, frame_to) = (F::from_frame_index(self.frames[from]), F::from_frame_index(self.frames[to]));
+            let frame_delta = (frame_to - frame_from) / fps;
+            (from + 1..to).fold(F::zero(), |error, index| {
+                let amount = (F::from_frame_index(self.frames[index]) - frame_from) / (frame_to - frame_from);
+                let value = T::hermite(&self.values[from], &slopes[from], &self.values[to], &slopes[to], amount, frame_delta);
+                error.max(distance(&self.values[index], &value))
+            })
+        });
+
+        let mut result = FrameCurve::curve_cubic_spline(self.design_frame_per_second);
+        for &index in keys.iter() {
+            result.curve_cubic_splice_frame(self.frames[index], self.values[index].clone(), slopes[index].clone(), slopes[index].clone());
+        }
+        (result, self.reduce_stats(&keys, max_error))
+    }
+
+    fn key_count(&self) -> usize {
+        self.frames.len().min(self.values.len())
+    }
+
+    fn tolerance_value<D: Fn(&T, &T) -> F>(&self, tolerance: ReduceTolerance<F>, distance: &D) -> F {
+        let range = self.values.iter().skip(1).fold(F::zero(), |range, value| range.max(distance(value, &self.values[0])));
+        tolerance.absolute.max(tolerance.relative * range)
+    }
+
+    fn reduce_stats(&self, keys: &[usize], max_error: F) -> ReduceStats<F> {
+        ReduceStats { keys_before: self.key_count(), keys_after: keys.len(), max_error }
+    }
+
+    /// 源曲线在关键帧处的斜率 (每秒), 内部取前后关键帧的差分, 两端取单侧差分
+    fn slope(&self, index: usize) -> T {
+        let last = self.key_count() - 1;
+        let zero = self.values[index].delta(&self.values[index]);
+        if last == 0 {
+            return zero;
+        }
+        let (pre, next) = (index.saturating_sub(1), (index + 1).min(last));
+        let seconds = (F::from_frame_index(self.frames[next]) - F::from_frame_index(self.frames[pre])) / F::from_frame_index(self.design_frame_per_second);
+        if seconds <= F::zero() {
+            return zero;
+        }
+        let delta = self.values[next].delta(&self.values[pre]);
+        zero.add_delta(&delta, F::one() / seconds)
+    }
+}
+
+impl<F: KeyFrameCurveScalar + FrameDataValue<F>> FrameCurve<F, F> {
+    /// 精简 FrameValues 曲线为 MinMaxCurve 曲线 (基础值 0, 缩放值 1)
+    /// 切线取源曲线在关键帧处的斜率, 按相邻保留关键帧的间隔换算
+    ///
+    /// * [tolerance] - 允许的误差
+    ///
+    pub fn reduce_minmax_curve(&self, tolerance: ReduceTolerance<F>) -> (FrameCurve<F, F>, ReduceStats<F>) {
+        let distance = |a: &F, b: &F| (*a - *b).abs();
+        let tolerance = self.tolerance_value(tolerance, &distance);
+        let fps = F::from_frame_index(self.design_frame_per_second);
+        // 每帧的斜率
+        let slopes: Vec<F> = (0..self.key_count()).map(|index| self.slope(index) / fps).collect();
+        let (keys, max_error) = reduce_keys(self.key_count(), tolerance, |from, to| {
+            let (frame_from, frame_to) = (F::from_frame_index(self.frames[from]), F::from_frame_index(self.frames[to]));
+            let frames = frame_to - frame_from;
+            (from + 1..to).fold(F::zero(), |error, index| {
+                let amount = (F::from_frame_index(self.frames[index]) - frame_from) / frames;
+                let value = hermite::hermite(self.values[from], slopes[from] * frames, self.values[to], slopes[to] * frames, amount);
+                error.max(distance(&self.values[index], &value))
+            })
+        });
+
+        let mut result = FrameCurve::curve_minmax_curve(F::zero(), F::one(), self.design_frame_per_second);
+        for (position, &index) in keys.iter().enumerate() {
+            let frame = self.frames[index];
+            let pre = keys[position.saturating_sub(1)];
+            let next = keys[(position + 1).min(keys.len() - 1)];
+            let intangent = slopes[index] * F::from_frame_index(frame - self.frames[pre]);
+            let outtangent = slopes[index] * F::from_frame_index(self.frames[next] - frame);
+            result.curve_minmax_curve_frame(frame, self.values[index], intangent, outtangent);
+        }
+        (result, self.reduce_stats(&keys, max_error))
+    }
+}
+
+/// 贪心精简 - 从保留的关键帧出发, 尽量延伸到误差不超限的最远关键帧
+///
+/// * [count] - 关键帧数量
+/// * [segment_error] - 仅保留首尾两帧时, 中间各帧的最大误差
+///
+/// 返回保留的关键帧序号与最大误差
+fn reduce_keys<F: KeyFrameCurveScalar, E: Fn(usize, usize) -> F>(count: usize, tolerance: F, segment_error: E) -> (Vec<usize>, F) {
+    let mut keys = Vec::new();
+    let mut max_error = F::zero();
+    if count == 0 {
+        return (keys, max_error);
+    }
+    let mut from = 0;
+    keys.push(from);
+    while from + 1 < count {
+        let (mut to, mut error) = (from + 1, segment_error(from, from + 1));
+        while to + 1 < count {
+            let next_error = segment_error(from, to + 1);
+            if next_error > tolerance {
+                break;
+            }
+            to += 1;
+            error = next_error;
+        }
+        max_error = max_error.max(error);
+        keys.push(to);
+        from = to;
+    }
+    (keys, max_error)
+}
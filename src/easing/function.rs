@@ -1,10 +1,10 @@
 //! 实现 Easing 缓动函数
 
-use nalgebra::RealField;
+use crate::float::CurveFloat;
 use crate::easing::EEasingMode;
 
 /// https://easings.net/# - 缓动函数实现
-pub fn easing_call<T: RealField + Copy>(x: T, mode: &EEasingMode) -> T {
+pub fn easing_call<T: CurveFloat>(x: T, mode: &EEasingMode) -> T {
     match mode {
         EEasingMode::None           => linear_in        (x),
         EEasingMode::BackIn         => back_in          (x),
@@ -40,7 +40,7 @@ pub fn easing_call<T: RealField + Copy>(x: T, mode: &EEasingMode) -> T {
     }
 }
 
-pub fn get_easing_call<T: RealField + Copy>(mode: EEasingMode) -> fn(T) -> T {
+pub fn get_easing_call<T: CurveFloat>(mode: EEasingMode) -> fn(T) -> T {
     match mode {
         EEasingMode::None           => linear_in        ,
         EEasingMode::BackIn         => back_in          ,
@@ -76,14 +76,14 @@ pub fn get_easing_call<T: RealField + Copy>(mode: EEasingMode) -> fn(T) -> T {
     }
 }
 
-pub fn back_in<T: RealField + Copy>(x: T) -> T {
+pub fn back_in<T: CurveFloat>(x: T) -> T {
     let xx = x * x;
     let c1 = T::from_f32(1.70158).unwrap();
     let c3 = c1 + T::one();
 
     return c3 * x * xx - c1 * xx;
 }
-pub fn back_out<T: RealField + Copy>(x: T) -> T {
+pub fn back_out<T: CurveFloat>(x: T) -> T {
     let c1 = T::from_f32(1.70158).unwrap();
     let c3 = c1 + T::one();
 
@@ -91,7 +91,7 @@ pub fn back_out<T: RealField + Copy>(x: T) -> T {
 
     return T::one() + c3 * temp.powi(3) + c1 * temp.powi(2);
 }
-pub fn back_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn back_in_out<T: CurveFloat>(x: T) -> T {
     let c1 = T::from_f32(1.70158).unwrap();
     let c2 = c1 * T::from_f32(1.525).unwrap();
 
@@ -107,10 +107,10 @@ pub fn back_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn bounce_in<T: RealField + Copy>(x: T) -> T {
+pub fn bounce_in<T: CurveFloat>(x: T) -> T {
     T::one() - bounce_out(T::one() - x)
 }
-pub fn bounce_out<T: RealField + Copy>(mut x: T) -> T {
+pub fn bounce_out<T: CurveFloat>(mut x: T) -> T {
     let n1 = T::from_f32(7.5625).unwrap();
     let d1 = T::from_f32(2.75).unwrap();
 
@@ -130,7 +130,7 @@ pub fn bounce_out<T: RealField + Copy>(mut x: T) -> T {
         return n1 * x * x + T::from_f32(0.984375).unwrap();
     }
 }
-pub fn bounce_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn bounce_in_out<T: CurveFloat>(x: T) -> T {
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
 
@@ -142,19 +142,19 @@ pub fn bounce_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn circle_in<T: RealField + Copy>(x: T) -> T {
+pub fn circle_in<T: CurveFloat>(x: T) -> T {
     let _0 = T::zero();
     let _1 = T::one();
 
     _1 - T::try_sqrt(_1 - T::powi(x, 2)).unwrap()
 }
-pub fn circle_out<T: RealField + Copy>(x: T) -> T {
+pub fn circle_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
 
     // _1 - circle_in(_1 - x)
     T::try_sqrt(_1 - T::powi(_1 - x, 2)).unwrap()
 }
-pub fn circle_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn circle_in_out<T: CurveFloat>(x: T) -> T {
     let _0_5 = T::from_f32(0.5).unwrap();
     let _2 = T::from_u8(2).unwrap();
     let _1 = T::one();
@@ -167,15 +167,15 @@ pub fn circle_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn cubic_in<T: RealField + Copy>(x: T) -> T {
+pub fn cubic_in<T: CurveFloat>(x: T) -> T {
     T::powi(x, 3)
 }
-pub fn cubic_out<T: RealField + Copy>(x: T) -> T {
+pub fn cubic_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
 
     _1 - cubic_in(_1 - x)
 }
-pub fn cubic_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn cubic_in_out<T: CurveFloat>(x: T) -> T {
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
 
@@ -190,15 +190,15 @@ pub fn cubic_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn quad_in<T: RealField + Copy>(x: T) -> T {
+pub fn quad_in<T: CurveFloat>(x: T) -> T {
     x * x
 }
-pub fn quad_out<T: RealField + Copy>(x: T) -> T {
+pub fn quad_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
 
     _1 - quad_in(_1 - x)
 }
-pub fn quad_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn quad_in_out<T: CurveFloat>(x: T) -> T {
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
     let _2 = T::from_u8(2).unwrap();
@@ -211,15 +211,15 @@ pub fn quad_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn quart_in<T: RealField + Copy>(x: T) -> T {
+pub fn quart_in<T: CurveFloat>(x: T) -> T {
     T::powi(x, 4)
 }
-pub fn quart_out<T: RealField + Copy>(x: T) -> T {
+pub fn quart_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
 
     _1 - T::powi(_1 - x, 4)
 }
-pub fn quart_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn quart_in_out<T: CurveFloat>(x: T) -> T {
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
     let _8 = T::from_u8(8).unwrap();
@@ -233,15 +233,15 @@ pub fn quart_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn quint_in<T: RealField + Copy>(x: T) -> T {
+pub fn quint_in<T: CurveFloat>(x: T) -> T {
     T::powi(x, 5)
 }
-pub fn quint_out<T: RealField + Copy>(x: T) -> T {
+pub fn quint_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
     
     _1 - quint_in(_1 - x)
 }
-pub fn quint_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn quint_in_out<T: CurveFloat>(x: T) -> T {
     let _0_5 = T::from_f32(0.5).unwrap();
     let _16 = T::from_u8(16).unwrap();
     let _1 = T::one();
@@ -254,25 +254,25 @@ pub fn quint_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn sine_in<T: RealField + Copy>(x: T) -> T {
+pub fn sine_in<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
     let _2 = T::from_u8(2).unwrap();
 
     _1 - (x * T::pi() / _2).cos()
 }
-pub fn sine_out<T: RealField + Copy>(x: T) -> T {
+pub fn sine_out<T: CurveFloat>(x: T) -> T {
     let _2 = T::from_u8(2).unwrap();
 
     (x * T::pi() / _2).sin()
 }
-pub fn sine_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn sine_in_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
     let _2 = T::from_u8(2).unwrap();
 
     -((x * T::pi()).cos() - _1) / _2
 }
 
-pub fn expo_in<T: RealField + Copy>(x: T) -> T {
+pub fn expo_in<T: CurveFloat>(x: T) -> T {
     if x == T::zero() {
         T::zero()
     }
@@ -282,7 +282,7 @@ pub fn expo_in<T: RealField + Copy>(x: T) -> T {
         _2.powc(_10 * x - _10)
     }
 }
-pub fn expo_out<T: RealField + Copy>(x: T) -> T {
+pub fn expo_out<T: CurveFloat>(x: T) -> T {
     let _1 = T::one();
     if x == _1 {
         T::one()
@@ -293,7 +293,7 @@ pub fn expo_out<T: RealField + Copy>(x: T) -> T {
         _1 - _2.powc(-_10 * x)
     }
 }
-pub fn expo_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn expo_in_out<T: CurveFloat>(x: T) -> T {
     
     let _1 = T::one();
     let _0_5 = T::from_f32(0.5).unwrap();
@@ -320,7 +320,7 @@ pub fn expo_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn elastic_in<T: RealField + Copy>(x: T) -> T {
+pub fn elastic_in<T: CurveFloat>(x: T) -> T {
     let _0 = T::zero();
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
@@ -342,7 +342,7 @@ pub fn elastic_in<T: RealField + Copy>(x: T) -> T {
         -_2.powc(_10 * x - _10) * ((_10 * x - _t) * c4).sin()
     }
 }
-pub fn elastic_out<T: RealField + Copy>(x: T) -> T {
+pub fn elastic_out<T: CurveFloat>(x: T) -> T {
     let _0 = T::zero();
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
@@ -365,7 +365,7 @@ pub fn elastic_out<T: RealField + Copy>(x: T) -> T {
         _2.powc(-_10 * x) * ((_10 * x - _t) * c4).sin() + _1
     }
 }
-pub fn elastic_in_out<T: RealField + Copy>(x: T) -> T {
+pub fn elastic_in_out<T: CurveFloat>(x: T) -> T {
     let _0 = T::zero();
     let _0_5 = T::from_f32(0.5).unwrap();
     let _1 = T::one();
@@ -393,6 +393,6 @@ pub fn elastic_in_out<T: RealField + Copy>(x: T) -> T {
     }
 }
 
-pub fn linear_in<T: RealField + Copy>(x: T) -> T {
+pub fn linear_in<T: CurveFloat>(x: T) -> T {
     x
 }
\ No newline at end of file
@@ -0,0 +1,87 @@
+//! 查表缓动 - 预先采样进度计算, 运行时查表代替 弹性/指数/贝塞尔 等较重的计算
+
+use alloc::vec::Vec;
+
+use crate::{amount::AnimationAmountCalc, curve::frame::{KeyFrameCurveScalar, KeyFrameCurveValue}, hermite::hermite};
+
+/// 估计误差时每个区间内的采样数
+pub const LUT_ERROR_SUBSAMPLES: usize = 8;
+
+/// 查表的插值方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ELutInterpolation {
+    /// 相邻两项线性插值
+    Linear,
+    /// Catmull-Rom 三次插值, 平滑曲线误差更小
+    Cubic,
+}
+
+/// 进度计算的查找表 - 在 [0, 1] 上等间隔采样 resolution + 1 项
+#[derive(Clone)]
+pub struct EasingLut<F: KeyFrameCurveScalar = KeyFrameCurveValue> {
+    source: AnimationAmountCalc<F>,
+    interpolation: ELutInterpolation,
+    values: Vec<F>,
+    /// 区间数量, 查表时将进度换算为序号
+    scale: F,
+    error_bound: F,
+}
+
+impl<F: KeyFrameCurveScalar> EasingLut<F> {
+    /// * [source] - 被采样的进度计算
+    /// * [resolution] - 区间数量, 至少为 1
+    /// * [interpolation] - 查表的插值方式
+    pub fn new(source: &AnimationAmountCalc<F>, resolution: usize, interpolation: ELutInterpolation) -> Self {
+        let resolution = resolution.max(1);
+        let step = F::one() / F::from_f64_value(resolution as f64);
+        let values = (0..=resolution).map(|index| source.calc(F::from_f64_value(index as f64) * step)).collect();
+        let mut lut = Self { source: source.clone(), interpolation, values, scale: F::from_f64_value(resolution as f64), error_bound: F::zero() };
+        lut.error_bound = lut.measure_error();
+        lut
+    }
+
+    pub fn source(&self) -> &AnimationAmountCalc<F> {
+        &self.source
+    }
+    pub fn interpolation(&self) -> ELutInterpolation {
+        self.interpolation
+    }
+    /// 区间数量
+    pub fn resolution(&self) -> usize {
+        self.values.len() - 1
+    }
+    pub fn values(&self) -> &[F] {
+        &self.values
+    }
+    /// 与源进度计算的最大误差 - 在每个区间内取 LUT_ERROR_SUBSAMPLES 个点估计
+    /// Steps 等不连续的进度计算在跳变处误差较大
+    pub fn error_bound(&self) -> F {
+        self.error_bound
+    }
+
+    /// 查表计算进度, 超出 [0, 1] 时取端点
+    pub fn calc(&self, amount: F) -> F {
+        let resolution = self.values.len() - 1;
+        let position = amount.clamp(F::zero(), F::one()) * self.scale;
+        let index = (position.to_f64_value() as usize).min(resolution - 1);
+        let t = position - F::from_f64_value(index as f64);
+        let (value1, value2) = (self.values[index], self.values[index + 1]);
+        match self.interpolation {
+            ELutInterpolation::Linear => value1 + (value2 - value1) * t,
+            ELutInterpolation::Cubic => {
+                let half = F::from_f64_value(0.5);
+                let tangent1 = if index == 0 { value2 - value1 } else { (value2 - self.values[index - 1]) * half };
+                let tangent2 = if index + 1 == resolution { value2 - value1 } else { (self.values[index + 2] - value1) * half };
+                hermite(value1, tangent1, value2, tangent2, t)
+            },
+        }
+    }
+
+    fn measure_error(&self) -> F {
+        let samples = (self.values.len() - 1) * LUT_ERROR_SUBSAMPLES;
+        (0..=samples).fold(F::zero(), |error, index| {
+            let amount = F::from_f64_value(index as f64 / samples as f64);
+            error.max((self.calc(amount) - self.source.calc(amount)).abs())
+        })
+    }
+}
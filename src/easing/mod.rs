@@ -47,4 +47,6 @@ pub enum EEasingMode {
     BounceInOut     = 0x33,
 }
 
-pub mod function;
\ No newline at end of file
+pub mod function;
+pub mod slice;
+pub mod lut;
\ No newline at end of file
@@ -0,0 +1,174 @@
+//! 批量缓动 - 对一组进度就地计算同一缓动
+//!
+//! 多项式类缓动 (Back/Bounce/Circle/Cubic/Quad/Quart/Quint) 按通道无分支计算,
+//! Sine/Expo/Elastic 依赖超越函数, 逐元素调用标量实现
+
+use crate::lanes::{map_lanes, F32x8};
+
+use super::{EEasingMode, function::*};
+
+const BACK_C1: f32 = 1.70158;
+const BACK_C2: f32 = BACK_C1 * 1.525;
+const BACK_C3: f32 = BACK_C1 + 1.;
+
+const BOUNCE_N1: f32 = 7.5625;
+const BOUNCE_D1: f32 = 2.75;
+
+/// 对 values 中每个进度就地计算缓动, 结果与 `easing_call` 一致 (误差在 f32 精度内)
+///
+/// Circle 类对超出 [0, 1] 的进度取 0 开方, 而非 panic (与 `easing_call` 不同), 与切片长度无关
+pub fn ease_slice(mode: EEasingMode, values: &mut [f32]) {
+    match mode {
+        EEasingMode::None           => {},
+        EEasingMode::BackIn         => map_lanes(values, back_in_lanes),
+        EEasingMode::BackOut        => map_lanes(values, back_out_lanes),
+        EEasingMode::BackInOut      => map_lanes(values, back_in_out_lanes),
+        EEasingMode::CircleIn       => map_lanes(values, circle_in_lanes),
+        EEasingMode::CircleOut      => map_lanes(values, circle_out_lanes),
+        EEasingMode::CircleInOut    => map_lanes(values, circle_in_out_lanes),
+        EEasingMode::CubicIn        => map_lanes(values, cubic_in_lanes),
+        EEasingMode::CubicOut       => map_lanes(values, cubic_out_lanes),
+        EEasingMode::CubicInOut     => map_lanes(values, cubic_in_out_lanes),
+        EEasingMode::QuadIn         => map_lanes(values, quad_in_lanes),
+        EEasingMode::QuadOut        => map_lanes(values, quad_out_lanes),
+        EEasingMode::QuadInOut      => map_lanes(values, quad_in_out_lanes),
+        EEasingMode::QuartIn        => map_lanes(values, quart_in_lanes),
+        EEasingMode::QuartOut       => map_lanes(values, quart_out_lanes),
+        EEasingMode::QuartInOut     => map_lanes(values, quart_in_out_lanes),
+        EEasingMode::QuintIn        => map_lanes(values, quint_in_lanes),
+        EEasingMode::QuintOut       => map_lanes(values, quint_out_lanes),
+        EEasingMode::QuintInOut     => map_lanes(values, quint_in_out_lanes),
+        EEasingMode::BounceIn       => map_lanes(values, bounce_in_lanes),
+        EEasingMode::BounceOut      => map_lanes(values, bounce_out_lanes),
+        EEasingMode::BounceInOut    => map_lanes(values, bounce_in_out_lanes),
+        _ => {
+            let call = get_easing_call::<f32>(mode);
+            values.iter_mut().for_each(|value| *value = call(*value));
+        },
+    }
+}
+
+#[inline(always)]
+fn back_in_lanes(x: F32x8) -> F32x8 {
+    let xx = x * x;
+    xx * x * BACK_C3 - xx * BACK_C1
+}
+#[inline(always)]
+fn back_out_lanes(x: F32x8) -> F32x8 {
+    let temp = x - 1.;
+    let squared = temp * temp;
+    squared * temp * BACK_C3 + squared * BACK_C1 + 1.
+}
+#[inline(always)]
+fn back_in_out_lanes(x: F32x8) -> F32x8 {
+    let x2 = x * 2.;
+    let low = x2 * x2 * (x2 * (BACK_C2 + 1.) - BACK_C2) * 0.5;
+    let temp = x2 - 2.;
+    let high = (temp * temp * (temp * (BACK_C2 + 1.) + BACK_C2) + 2.) * 0.5;
+    low.select(x.lt(0.5), high)
+}
+
+#[inline(always)]
+fn bounce_out_lanes(x: F32x8) -> F32x8 {
+    let part = |offset: f32, base: f32| {
+        let temp = x - offset / BOUNCE_D1;
+        temp * temp * BOUNCE_N1 + base
+    };
+    let result = part(2.625, 0.984375);
+    let result = part(2.25, 0.9375).select(x.lt(2.5 / BOUNCE_D1), result);
+    let result = part(1.5, 0.75).select(x.lt(2.0 / BOUNCE_D1), result);
+    (x * x * BOUNCE_N1).select(x.lt(1.0 / BOUNCE_D1), result)
+}
+#[inline(always)]
+fn bounce_in_lanes(x: F32x8) -> F32x8 {
+    -bounce_out_lanes(-x + 1.) + 1.
+}
+#[inline(always)]
+fn bounce_in_out_lanes(x: F32x8) -> F32x8 {
+    let x2 = x * 2.;
+    let low = (-bounce_out_lanes(-x2 + 1.) + 1.) * 0.5;
+    let high = (bounce_out_lanes(x2 - 1.) + 1.) * 0.5;
+    low.select(x.lt(0.5), high)
+}
+
+#[inline(always)]
+fn circle_in_lanes(x: F32x8) -> F32x8 {
+    -(-(x * x) + 1.).sqrt() + 1.
+}
+#[inline(always)]
+fn circle_out_lanes(x: F32x8) -> F32x8 {
+    let temp = -x + 1.;
+    (-(temp * temp) + 1.).sqrt()
+}
+#[inline(always)]
+fn circle_in_out_lanes(x: F32x8) -> F32x8 {
+    let low = circle_in_lanes(x * 2.) * 0.5;
+    let temp = -(x * 2.) + 2.;
+    let high = ((-(temp * temp) + 1.).sqrt() + 1.) * 0.5;
+    low.select(x.lt(0.5), high)
+}
+
+#[inline(always)]
+fn cubic_in_lanes(x: F32x8) -> F32x8 {
+    x * x * x
+}
+#[inline(always)]
+fn cubic_out_lanes(x: F32x8) -> F32x8 {
+    -cubic_in_lanes(-x + 1.) + 1.
+}
+#[inline(always)]
+fn cubic_in_out_lanes(x: F32x8) -> F32x8 {
+    let xx = x * x;
+    let low = xx * xx * x * 16.;
+    let temp = -(x * 2.) + 2.;
+    let high = -(temp * temp * temp * 0.5) + 1.;
+    low.select(x.lt(0.5), high)
+}
+
+#[inline(always)]
+fn quad_in_lanes(x: F32x8) -> F32x8 {
+    x * x
+}
+#[inline(always)]
+fn quad_out_lanes(x: F32x8) -> F32x8 {
+    -quad_in_lanes(-x + 1.) + 1.
+}
+#[inline(always)]
+fn quad_in_out_lanes(x: F32x8) -> F32x8 {
+    let low = x * x * 2.;
+    let temp = (-x + 1.) * 2.;
+    let high = -(temp * temp * 0.5) + 1.;
+    low.select(x.lt(0.5), high)
+}
+
+#[inline(always)]
+fn quart_in_lanes(x: F32x8) -> F32x8 {
+    let xx = x * x;
+    xx * xx
+}
+#[inline(always)]
+fn quart_out_lanes(x: F32x8) -> F32x8 {
+    -quart_in_lanes(-x + 1.) + 1.
+}
+#[inline(always)]
+fn quart_in_out_lanes(x: F32x8) -> F32x8 {
+    let low = quart_in_lanes(x) * 8.;
+    let high = -(quart_in_lanes(-(x * 2.) + 2.) * 0.5) + 1.;
+    low.select(x.lt(0.5), high)
+}
+
+#[inline(always)]
+fn quint_in_lanes(x: F32x8) -> F32x8 {
+    let xx = x * x;
+    xx * xx * x
+}
+#[inline(always)]
+fn quint_out_lanes(x: F32x8) -> F32x8 {
+    -quint_in_lanes(-x + 1.) + 1.
+}
+#[inline(always)]
+fn quint_in_out_lanes(x: F32x8) -> F32x8 {
+    let low = quint_in_lanes(x) * 16.;
+    let high = -(quint_in_lanes(-(x * 2.) + 2.) * 0.5) + 1.;
+    low.select(x.lt(0.5), high)
+}
@@ -0,0 +1,143 @@
+//! 曲线计算所需的浮点运算
+//!
+//! 缓动/贝塞尔/Hermite 等函数只依赖此处的少量运算, 无需 nalgebra 即可使用
+
+use core::{fmt::Debug, ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign}};
+
+pub trait CurveFloat:
+    Copy
+    + PartialOrd
+    + Debug
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn pi() -> Self;
+    fn from_f32(value: f32) -> Option<Self>;
+    fn from_u8(value: u8) -> Option<Self>;
+    fn from_usize(value: usize) -> Option<Self>;
+    fn powi(self, n: i32) -> Self;
+    /// 实数幂
+    fn powc(self, n: Self) -> Self;
+    /// 负数返回 None
+    fn try_sqrt(self) -> Option<Self>;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn acos(self) -> Self;
+    fn floor(self) -> Self;
+    fn abs(self) -> Self;
+    fn min(self, other: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn clamp(self, min: Self, max: Self) -> Self;
+}
+
+/// 有 std 时使用标准库实现, 否则使用 libm
+#[cfg(feature = "std")]
+macro_rules! math {
+    ($std:expr, $libm:expr) => {
+        $std
+    };
+}
+#[cfg(not(feature = "std"))]
+macro_rules! math {
+    ($std:expr, $libm:expr) => {
+        $libm
+    };
+}
+
+macro_rules! impl_curve_float {
+    ($($ty:ident: $powf:ident, $sqrt:ident, $sin:ident, $cos:ident, $acos:ident, $floor:ident, $fabs:ident;)*) => {
+        $(
+            impl CurveFloat for $ty {
+                #[inline]
+                fn zero() -> Self {
+                    0.
+                }
+                #[inline]
+                fn one() -> Self {
+                    1.
+                }
+                #[inline]
+                fn pi() -> Self {
+                    core::$ty::consts::PI
+                }
+                #[inline]
+                fn from_f32(value: f32) -> Option<Self> {
+                    Some(value as $ty)
+                }
+                #[inline]
+                fn from_u8(value: u8) -> Option<Self> {
+                    Some(value as $ty)
+                }
+                #[inline]
+                fn from_usize(value: usize) -> Option<Self> {
+                    Some(value as $ty)
+                }
+                #[inline]
+                fn powi(self, n: i32) -> Self {
+                    math!($ty::powi(self, n), libm::$powf(self, n as $ty))
+                }
+                #[inline]
+                fn powc(self, n: Self) -> Self {
+                    math!($ty::powf(self, n), libm::$powf(self, n))
+                }
+                #[inline]
+                fn try_sqrt(self) -> Option<Self> {
+                    if self >= 0. {
+                        Some(math!($ty::sqrt(self), libm::$sqrt(self)))
+                    } else {
+                        None
+                    }
+                }
+                #[inline]
+                fn sin(self) -> Self {
+                    math!($ty::sin(self), libm::$sin(self))
+                }
+                #[inline]
+                fn cos(self) -> Self {
+                    math!($ty::cos(self), libm::$cos(self))
+                }
+                #[inline]
+                fn acos(self) -> Self {
+                    math!($ty::acos(self), libm::$acos(self))
+                }
+                #[inline]
+                fn floor(self) -> Self {
+                    math!($ty::floor(self), libm::$floor(self))
+                }
+                #[inline]
+                fn abs(self) -> Self {
+                    math!($ty::abs(self), libm::$fabs(self))
+                }
+                #[inline]
+                fn min(self, other: Self) -> Self {
+                    $ty::min(self, other)
+                }
+                #[inline]
+                fn max(self, other: Self) -> Self {
+                    $ty::max(self, other)
+                }
+                #[inline]
+                fn clamp(self, min: Self, max: Self) -> Self {
+                    $ty::clamp(self, min, max)
+                }
+            }
+        )*
+    };
+}
+
+impl_curve_float! {
+    f32: powf, sqrtf, sinf, cosf, acosf, floorf, fabsf;
+    f64: pow, sqrt, sin, cos, acos, floor, fabs;
+}
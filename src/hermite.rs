@@ -1,8 +1,8 @@
 //!  实现 hermite 曲线相关函数
 
-use nalgebra::RealField;
+use crate::{float::CurveFloat, lanes::{map_lanes, F32x8}};
 
-pub fn hermite<T: RealField + Copy>(value1: T, tangent1: T, value2: T, tangent2: T, amount: T) -> T {
+pub fn hermite<T: CurveFloat>(value1: T, tangent1: T, value2: T, tangent2: T, amount: T) -> T {
     let _1 = T::from_u8(1).unwrap();
     let _2 = T::from_u8(2).unwrap();
     let _3 = T::from_u8(3).unwrap();
@@ -15,4 +15,14 @@ pub fn hermite<T: RealField + Copy>(value1: T, tangent1: T, value2: T, tangent2:
     let part4 = cubed - squared;
 
     return (((value1 * part1) + (value2 * part2)) + (tangent1 * part3)) + (tangent2 * part4);
-}
\ No newline at end of file
+}
+
+/// 同一段 hermite 曲线在一组进度上的批量计算, amounts 就地替换为结果
+/// 尾部同样经由通道计算, 结果与元素在切片中的位置无关
+pub fn hermite_slice(value1: f32, tangent1: f32, value2: f32, tangent2: f32, amounts: &mut [f32]) {
+    // 展开为关于 amount 的三次多项式 a t^3 + b t^2 + c t + d
+    let cubed = 2. * value1 - 2. * value2 + tangent1 + tangent2;
+    let squared = -3. * value1 + 3. * value2 - 2. * tangent1 - tangent2;
+    let kernel = |amount: F32x8| ((amount * cubed + squared) * amount + tangent1) * amount + value1;
+    map_lanes(amounts, kernel);
+}
@@ -0,0 +1,113 @@
+//! 定宽 f32 通道 - 以固定长度数组表达逐通道运算, 由编译器自动向量化
+//!
+//! 不依赖 nightly 的 portable SIMD, 各运算均为无分支的逐通道循环
+
+use core::ops::{Add, Mul, Neg, Sub};
+
+/// 通道宽度 - 覆盖 AVX 的 8 x f32, SSE/NEON 下拆为两次运算
+pub(crate) const LANES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct F32x8(pub [f32; LANES]);
+
+/// 逐通道比较结果
+pub(crate) type Mask8 = [bool; LANES];
+
+impl F32x8 {
+    #[inline(always)]
+    pub fn load(values: &[f32]) -> Self {
+        let mut result = [0.; LANES];
+        result.copy_from_slice(&values[..LANES]);
+        Self(result)
+    }
+    #[inline(always)]
+    pub fn store(self, values: &mut [f32]) {
+        values[..LANES].copy_from_slice(&self.0);
+    }
+    #[inline(always)]
+    pub fn map<C: Fn(f32) -> f32>(self, call: C) -> Self {
+        Self(self.0.map(call))
+    }
+    #[inline(always)]
+    pub fn lt(self, other: f32) -> Mask8 {
+        self.0.map(|value| value < other)
+    }
+    /// mask 为 true 的通道取 self, 否则取 other
+    #[inline(always)]
+    pub fn select(self, mask: Mask8, other: Self) -> Self {
+        let mut result = other.0;
+        for index in 0..LANES {
+            if mask[index] {
+                result[index] = self.0[index];
+            }
+        }
+        Self(result)
+    }
+    /// 负数通道取 0
+    #[inline(always)]
+    pub fn sqrt(self) -> Self {
+        self.map(|value| {
+            let value = if value < 0. { 0. } else { value };
+            #[cfg(feature = "std")]
+            { value.sqrt() }
+            #[cfg(not(feature = "std"))]
+            { libm::sqrtf(value) }
+        })
+    }
+}
+
+macro_rules! lanes_op {
+    ($($trait:ident, $call:ident, $op:tt, $assign:tt;)*) => {
+        $(
+            impl $trait for F32x8 {
+                type Output = Self;
+                #[inline(always)]
+                fn $call(self, rhs: Self) -> Self {
+                    let mut result = self.0;
+                    for index in 0..LANES {
+                        result[index] $assign rhs.0[index];
+                    }
+                    Self(result)
+                }
+            }
+            impl $trait<f32> for F32x8 {
+                type Output = Self;
+                #[inline(always)]
+                fn $call(self, rhs: f32) -> Self {
+                    self.map(|value| value $op rhs)
+                }
+            }
+        )*
+    };
+}
+
+lanes_op! {
+    Add, add, +, +=;
+    Sub, sub, -, -=;
+    Mul, mul, *, *=;
+}
+
+impl Neg for F32x8 {
+    type Output = Self;
+    #[inline(always)]
+    fn neg(self) -> Self {
+        self.map(|value| -value)
+    }
+}
+
+/// 按 LANES 分块计算, 不足一块的尾部补 0 后同样经由通道函数计算
+/// 结果与元素在切片中的位置无关
+#[inline(always)]
+pub(crate) fn map_lanes<K: Fn(F32x8) -> F32x8>(values: &mut [f32], kernel: K) {
+    let mut chunks = values.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        kernel(F32x8::load(chunk)).store(chunk);
+    }
+    let remainder = chunks.into_remainder();
+    if !remainder.is_empty() {
+        let mut tail = [0.; LANES];
+        tail[..remainder.len()].copy_from_slice(remainder);
+        let result = kernel(F32x8(tail));
+        remainder.copy_from_slice(&result.0[..remainder.len()]);
+    }
+}
@@ -1,26 +1,41 @@
-use curve::frame::KeyFrameCurveValue;
-use easing::{EEasingMode, function::*};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use curve::frame::{KeyFrameCurveScalar, KeyFrameCurveValue};
+use easing::{EEasingMode, function::*, lut::ELutInterpolation};
 use steps::EStepMode;
 
 #[cfg(feature = "nalgebra")]
 extern crate nalgebra;
+#[cfg(feature = "glam")]
+extern crate glam;
+#[cfg(feature = "mint")]
+extern crate mint;
 
+#[cfg(feature = "derive")]
+pub use pi_curves_derive::FrameDataValue;
+
+pub mod float;
 pub mod hermite;
 pub mod bezier;
 pub mod easing;
 pub mod curve;
 pub mod steps;
 pub mod amount;
+pub mod animation;
+
+mod lanes;
 
 /// 进度计算参数
 /// 对于 Step 模式 第一个参数为 步进数目
 /// 对于 CubicBezier 模式 四个参数分别对应 (x1, y1, x2, y2)
 #[derive(Debug, Clone, Copy)]
-pub struct AmountParam(pub KeyFrameCurveValue, pub KeyFrameCurveValue, pub KeyFrameCurveValue, pub KeyFrameCurveValue);
+pub struct AmountParam<F: KeyFrameCurveScalar = KeyFrameCurveValue>(pub F, pub F, pub F, pub F);
 
-impl Default for AmountParam {
+impl<F: KeyFrameCurveScalar> Default for AmountParam<F> {
     fn default() -> Self {
-        Self( 0., 0., 0., 0. )
+        Self( F::zero(), F::zero(), F::zero(), F::zero() )
     }
 }
 
@@ -31,19 +46,41 @@ pub enum EAmountMode {
     Easing(EEasingMode),
     Steps(EStepMode),
     CubicBezier,
+    /// 查表, 查找表由 `AnimationAmountCalc::from_lut` 提供
+    Lut(ELutInterpolation),
 }
 
 impl EAmountMode {
-    pub fn get_transform_amount_call(mode: EAmountMode) -> fn(KeyFrameCurveValue, &AmountParam) -> KeyFrameCurveValue {
+    /// 模式对应的进度计算函数
+    /// Lut 模式的查找表不在参数中, 返回的函数不做变换; 查表请使用 `AnimationAmountCalc::from_lut`
+    #[deprecated(note = "使用 `transform_amount` (以 match 分发) 或 `AnimationAmountCalc::calc` (支持 Lut)")]
+    pub fn get_transform_amount_call<F: KeyFrameCurveScalar>(mode: EAmountMode) -> fn(F, &AmountParam<F>) -> F {
         match mode {
             EAmountMode::None => Self::calc_amount_none,
             EAmountMode::Easing(mode) => Self::get_calc_amount_easing(mode),
             EAmountMode::Steps(mode) => Self::get_calc_amount_steps(mode),
             EAmountMode::CubicBezier => Self::calc_amount_cubic_bezier,
+            // 查找表不在参数中, 由 AnimationAmountCalc 查表
+            EAmountMode::Lut(_) => Self::calc_amount_none,
         }
     }
     
-    fn get_calc_amount_easing(mode: EEasingMode) -> fn(KeyFrameCurveValue, &AmountParam) -> KeyFrameCurveValue {
+    /// 按模式计算进度 - 以 match 分发, 调用处可内联
+    /// Lut 模式的查找表不在参数中, 此处不做变换, 由 `AnimationAmountCalc` 查表
+    #[inline]
+    pub fn transform_amount<F: KeyFrameCurveScalar>(self, amount: F, param: &AmountParam<F>) -> F {
+        match self {
+            EAmountMode::None | EAmountMode::Lut(_) => amount,
+            EAmountMode::Easing(mode) => easing_call(amount, &mode),
+            EAmountMode::Steps(EStepMode::JumpStart) => amount_step_start(amount, param),
+            EAmountMode::Steps(EStepMode::JumpEnd) => amount_step_end(amount, param),
+            EAmountMode::Steps(EStepMode::JumpNone) => amount_step_none(amount, param),
+            EAmountMode::Steps(EStepMode::JumpBoth) => amount_step_both(amount, param),
+            EAmountMode::CubicBezier => bezier::cubic_bezier(param.0, param.1, param.2, param.3, amount),
+        }
+    }
+
+    fn get_calc_amount_easing<F: KeyFrameCurveScalar>(mode: EEasingMode) -> fn(F, &AmountParam<F>) -> F {
         match mode {
             EEasingMode::None           => amount_linear_in        ,
             EEasingMode::BackIn         => amount_back_in          ,
@@ -80,7 +117,7 @@ impl EAmountMode {
     }
 
 
-    fn get_calc_amount_steps(mode: EStepMode) -> fn(KeyFrameCurveValue, &AmountParam) -> KeyFrameCurveValue {
+    fn get_calc_amount_steps<F: KeyFrameCurveScalar>(mode: EStepMode) -> fn(F, &AmountParam<F>) -> F {
         match mode {
             EStepMode::JumpStart => amount_step_start,
             EStepMode::JumpEnd => amount_step_end,
@@ -89,11 +126,11 @@ impl EAmountMode {
         }
     }
 
-    fn calc_amount_none(amount: KeyFrameCurveValue, _param: &AmountParam) -> KeyFrameCurveValue{
+    fn calc_amount_none<F: KeyFrameCurveScalar>(amount: F, _param: &AmountParam<F>) -> F {
         amount
     }
 
-    fn calc_amount_cubic_bezier(amount: KeyFrameCurveValue, param: &AmountParam) -> KeyFrameCurveValue {
+    fn calc_amount_cubic_bezier<F: KeyFrameCurveScalar>(amount: F, param: &AmountParam<F>) -> F {
         let x1 = param.0;
         let y1 = param.1;
         let x2 = param.2;
@@ -102,138 +139,138 @@ impl EAmountMode {
     }
 }
 
-pub fn amount_step_start(x: KeyFrameCurveValue, param: &AmountParam) -> KeyFrameCurveValue {
-    let t = 1.0 / param.0 as KeyFrameCurveValue;
+pub fn amount_step_start<F: KeyFrameCurveScalar>(x: F, param: &AmountParam<F>) -> F {
+    let t = F::one() / param.0;
     let ix = (x / t).floor();
     // println!("x: {}, t: {}, ix {}", x, t, ix);
-    let ix = (ix + 1.) * t;
+    let ix = (ix + F::one()) * t;
 
     ix
 }
-pub fn amount_step_end(x: KeyFrameCurveValue, param: &AmountParam) -> KeyFrameCurveValue {
-    let t = 1.0 / param.0 as KeyFrameCurveValue;
+pub fn amount_step_end<F: KeyFrameCurveScalar>(x: F, param: &AmountParam<F>) -> F {
+    let t = F::one() / param.0;
     let ix = (x / t).floor();
     let ix = ix * t;
 
     ix
 }
-pub fn amount_step_none(x: KeyFrameCurveValue, param: &AmountParam) -> KeyFrameCurveValue {
-    let t = 1.0 / param.0 as KeyFrameCurveValue;
+pub fn amount_step_none<F: KeyFrameCurveScalar>(x: F, param: &AmountParam<F>) -> F {
+    let t = F::one() / param.0;
     let ix = (x / t).floor();
-    let t = 1.0 / (param.0 as KeyFrameCurveValue - 1.0);
+    let t = F::one() / (param.0 - F::one());
     let ix = ix * t;
 
     ix
 }
-pub fn amount_step_both(x: KeyFrameCurveValue, param: &AmountParam) -> KeyFrameCurveValue {
-    let t = 1.0 / param.0 as KeyFrameCurveValue;
+pub fn amount_step_both<F: KeyFrameCurveScalar>(x: F, param: &AmountParam<F>) -> F {
+    let t = F::one() / param.0;
     let ix = (x / t).floor();
-    let t = 1.0 / (param.0 as KeyFrameCurveValue + 1.0);
-    let ix = (ix + 1.0) * t;
+    let t = F::one() / (param.0 + F::one());
+    let ix = (ix + F::one()) * t;
 
     ix
 }
 
-pub fn amount_back_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_back_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     back_in(x)
 }
-pub fn amount_back_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_back_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     back_out(x)
 }
-pub fn amount_back_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_back_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     back_in_out(x)
 }
 
-pub fn amount_bounce_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_bounce_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     bounce_in(x)
 }
-pub fn amount_bounce_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_bounce_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     bounce_out(x)
 }
-pub fn amount_bounce_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_bounce_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     bounce_in_out(x)
 }
 
-pub fn amount_circle_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_circle_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     circle_in(x)
 }
-pub fn amount_circle_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_circle_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     circle_out(x)
 }
-pub fn amount_circle_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_circle_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     circle_in_out(x)
 }
 
-pub fn amount_cubic_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_cubic_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     cubic_in(x)
 }
-pub fn amount_cubic_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_cubic_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     cubic_out(x)
 }
-pub fn amount_cubic_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_cubic_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     cubic_in_out(x)
 }
 
-pub fn amount_quad_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quad_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quad_in(x)
 }
-pub fn amount_quad_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quad_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quad_out(x)
 }
-pub fn amount_quad_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quad_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quad_in_out(x)
 }
 
-pub fn amount_quart_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quart_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quart_in(x)
 }
-pub fn amount_quart_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quart_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quart_out(x)
 }
-pub fn amount_quart_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quart_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quart_in_out(x)
 }
 
-pub fn amount_quint_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quint_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quint_in(x)
 }
-pub fn amount_quint_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quint_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quint_out(x)
 }
-pub fn amount_quint_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_quint_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     quint_in_out(x)
 }
 
-pub fn amount_sine_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_sine_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     sine_in(x)
 }
-pub fn amount_sine_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_sine_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     sine_out(x)
 }
-pub fn amount_sine_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_sine_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     sine_in_out(x)
 }
 
-pub fn amount_expo_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_expo_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     expo_in(x)
 }
-pub fn amount_expo_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_expo_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     expo_out(x)
 }
-pub fn amount_expo_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_expo_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     expo_in_out(x)
 }
 
-pub fn amount_elastic_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_elastic_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     elastic_in(x)
 }
-pub fn amount_elastic_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_elastic_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     elastic_out(x)
 }
-pub fn amount_elastic_in_out(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_elastic_in_out<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     elastic_in_out(x)
 }
 
-pub fn amount_linear_in(x: KeyFrameCurveValue, _: &AmountParam) -> KeyFrameCurveValue {
+pub fn amount_linear_in<F: KeyFrameCurveScalar>(x: F, _: &AmountParam<F>) -> F {
     linear_in(x)
 }
\ No newline at end of file
@@ -0,0 +1,570 @@
+use std::any::Any;
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{Quaternion, UnitQuaternion};
+
+use pi_curves::{amount::AnimationAmountCalc, animation::{blend::{blend, AnimationBlender, EBlendMode}, blend_tree::{BlendTree1D, BlendTree2D, EBlendTree2DMode}, clip::AnimationClip, event::{AnimationEvent, AnimationEventTrack, MAX_CROSSED_ITERATIONS}, layer::{AnimationLayer, AnimationLayerStack, AnimationMask}, state_machine::{AnimationState, AnimationStateMachine, AnimationTransition, EAnimationCondition, EAnimationMotion}, timeline::{Timeline, TimelineItem}, tween::Tween, pose::AnimationPose, player::{AnimationPlayer, EAnimationPhase, EFillMode, EIterationCount, EPlaybackDirection}}, curve::{frame::{FrameDataValue, KeyFrameDataType, KeyFrameDataTypeAllocator}, frame_curve::FrameCurve, ErrorCurve, FrameIndex}, easing::EEasingMode};
+
+#[test]
+fn test_clip_sample() {
+    let mut allocator = KeyFrameDataTypeAllocator::default();
+    let float_type = allocator.alloc().unwrap();
+    let array_type = allocator.alloc().unwrap();
+
+    let mut clip: AnimationClip = AnimationClip::new(60);
+
+    let mut alpha = FrameCurve::curve_frame_values(60);
+    alpha.curve_frame_values_frame(0, 0.0f32);
+    alpha.curve_frame_values_frame(30, 1.0f32);
+    clip.add_curve("node/sprite", "alpha", float_type, alpha).unwrap();
+
+    let position = FrameCurve::curve_easing([0.0f32, 0.], [10., 20.], 120, 60, EEasingMode::None);
+    clip.add_curve("node", "position", array_type, position).unwrap();
+
+    assert_eq!(clip.max_frame(), 120);
+    assert_eq!(clip.duration(), 2.0);
+    assert_eq!(clip.channels().len(), 2);
+
+    let mut samples: Vec<(String, String, KeyFrameDataType, Vec<f32>)> = vec![];
+    let mut sink = |target: &str, property: &str, data_type: KeyFrameDataType, value: &dyn Any| {
+        let value = if data_type == float_type {
+            vec![*value.downcast_ref::<f32>().unwrap()]
+        } else {
+            value.downcast_ref::<[f32; 2]>().unwrap().to_vec()
+        };
+        samples.push((target.to_string(), property.to_string(), data_type, value));
+    };
+    clip.sample(0.25, &AnimationAmountCalc::default(), &mut sink);
+
+    assert_eq!(samples, vec![
+        ("node/sprite".to_string(), "alpha".to_string(), float_type, vec![0.5]),
+        ("node".to_string(), "position".to_string(), array_type, vec![1.25, 2.5]),
+    ]);
+
+    assert!(clip.curve::<f32>("node/sprite", "alpha").is_some());
+    assert!(clip.curve::<f64>("node/sprite", "alpha").is_none());
+
+    assert!(clip.remove_curve("node", "position"));
+    assert_eq!(clip.max_frame(), 30);
+}
+
+#[test]
+fn test_clip_errors() {
+    let mut clip: AnimationClip = AnimationClip::new(60);
+    clip.add_curve("node", "alpha", 0, FrameCurve::curve_easing(0.0f32, 1.0, 60, 60, EEasingMode::None)).unwrap();
+
+    let result = clip.add_curve("node", "alpha", 0, FrameCurve::curve_easing(0.0f32, 1.0, 60, 60, EEasingMode::None));
+    assert!(matches!(result, Err(ErrorCurve::ChannelAlreadyExists)));
+
+    let result = clip.add_curve("node", "frame", 0, FrameCurve::curve_easing(0u8, 1, 60, 60, EEasingMode::None));
+    assert!(matches!(result, Err(ErrorCurve::KeyFrameDataTypeMismatch)));
+
+    let result = clip.add_curve("node", "scale", 0, FrameCurve::curve_easing(0.0f32, 1.0, 30, 30, EEasingMode::None));
+    assert!(matches!(result, Err(ErrorCurve::FramePerSecondMismatch)));
+}
+
+#[test]
+fn test_player_timing() {
+    let mut player: AnimationPlayer = AnimationPlayer::new(1.0);
+    player.delay = 0.5;
+    player.iterations = EIterationCount::Count(2.5);
+    player.direction = EPlaybackDirection::Alternate;
+
+    assert_eq!(player.end_time(), Some(3.0));
+    assert_eq!(player.phase(), EAnimationPhase::Before);
+    assert_eq!(player.progress(), None);
+
+    player.tick(0.75);
+    assert_eq!(player.phase(), EAnimationPhase::Active);
+    assert_eq!(player.current_iteration(), Some(0.));
+    assert_eq!(player.progress(), Some(0.25));
+
+    // 第二次迭代反向
+    player.seek(1.75);
+    assert_eq!(player.current_iteration(), Some(1.));
+    assert_eq!(player.progress(), Some(0.75));
+
+    // 小数次迭代在 0.5 处结束
+    player.seek(3.5);
+    assert_eq!(player.phase(), EAnimationPhase::After);
+    assert!(player.is_finished());
+    assert_eq!(player.progress(), None);
+    player.fill = EFillMode::Forwards;
+    assert_eq!(player.current_iteration(), Some(2.));
+    assert_eq!(player.progress(), Some(0.5));
+
+    // 整数次迭代结束时保持在迭代末尾
+    player.iterations = EIterationCount::Count(2.);
+    assert_eq!(player.current_iteration(), Some(1.));
+    assert_eq!(player.progress(), Some(0.));
+    player.direction = EPlaybackDirection::Normal;
+    assert_eq!(player.progress(), Some(1.));
+
+    player.seek(0.);
+    assert_eq!(player.progress(), None);
+    player.fill = EFillMode::Both;
+    assert_eq!(player.progress(), Some(0.));
+    player.direction = EPlaybackDirection::AlternateReverse;
+    assert_eq!(player.progress(), Some(1.));
+}
+
+#[test]
+fn test_player_reverse_and_infinite() {
+    let curve = FrameCurve::curve_easing(0.0f32, 10.0, 60, 60, EEasingMode::None);
+    let amountcalc = AnimationAmountCalc::default();
+
+    let mut player = AnimationPlayer::from_curve(&curve);
+    player.playback_rate = -2.;
+    player.restart();
+    assert_eq!(player.current_time(), 1.0);
+    assert_eq!(player.sample(&curve, &amountcalc), Some(10.0));
+
+    player.tick(0.25);
+    assert_eq!(player.sample(&curve, &amountcalc), Some(5.0));
+    player.tick(0.25);
+    assert_eq!(player.phase(), EAnimationPhase::Before);
+    assert!(player.is_finished());
+    assert_eq!(player.sample(&curve, &amountcalc), None);
+
+    player.playback_rate = 1.;
+    player.iterations = EIterationCount::Infinite;
+    player.direction = EPlaybackDirection::Reverse;
+    assert_eq!(player.end_time(), None);
+    player.seek(1000.25);
+    assert_eq!(player.phase(), EAnimationPhase::Active);
+    assert_eq!(player.current_iteration(), Some(1000.));
+    assert_eq!(player.sample(&curve, &amountcalc), Some(7.5));
+}
+
+#[test]
+fn test_event_crossed() {
+    let mut track = AnimationEventTrack::new(10);
+    track.add(5, "step_right");
+    track.add(0, "start");
+    track.add(10, "end");
+    track.add(5, "step_sfx");
+
+    let names = |events: Vec<&AnimationEvent<&'static str>>| events.iter().map(|event| event.payload).collect::<Vec<_>>();
+
+    let mut player: AnimationPlayer = AnimationPlayer::new(1.0);
+    player.iterations = EIterationCount::Count(3.);
+
+    assert_eq!(names(track.crossed(&player, 0., 0.5)), vec!["start", "step_right", "step_sfx"]);
+    assert_eq!(names(track.crossed(&player, 0.5, 0.75)), Vec::<&str>::new());
+    // 连续查询在迭代交界处不重复也不遗漏
+    assert_eq!(names(track.crossed(&player, 0.75, 1.0)), vec!["end", "start"]);
+    assert_eq!(names(track.crossed(&player, 1.0, 1.5)), vec!["step_right", "step_sfx"]);
+    // 大跨度跳转经过多次迭代, 超出结尾部分被截断
+    assert_eq!(names(track.crossed(&player, 1.5, 10.)), vec!["end", "start", "step_right", "step_sfx", "end"]);
+
+    // 反向播放
+    assert_eq!(names(track.crossed(&player, 3., 2.5)), vec!["end", "step_sfx", "step_right"]);
+    assert_eq!(names(track.crossed(&player, 2.5, 2.)), vec!["start"]);
+    assert_eq!(names(track.crossed(&player, 2., 1.75)), vec!["end"]);
+
+    // 交替方向: 第二次迭代从尾到头
+    player.direction = EPlaybackDirection::Alternate;
+    player.delay = 1.;
+    assert_eq!(names(track.crossed(&player, 0., 1.)), Vec::<&str>::new());
+    assert_eq!(names(track.crossed(&player, 1., 1.25)), vec!["start"]);
+    assert_eq!(names(track.crossed(&player, 1.75, 2.25)), vec!["end", "end"]);
+    assert_eq!(names(track.crossed(&player, 2.25, 3.)), vec!["step_sfx", "step_right", "start", "start"]);
+
+    player.iterations = EIterationCount::Infinite;
+    // 1000 次完整迭代, 终点恰好进入下一次迭代的开头
+    assert_eq!(track.crossed(&player, 1., 1001.).len(), 1000 * 4 + 1);
+}
+
+#[test]
+fn test_event_crossed_large_jump() {
+    let mut track = AnimationEventTrack::new(10);
+    track.add(0, "start");
+    track.add(5, "middle");
+    track.add(10, "end");
+
+    let mut player: AnimationPlayer = AnimationPlayer::new(1.0);
+    player.iterations = EIterationCount::Infinite;
+    // 超出 f32 可精确计数的迭代次数, 完整迭代按上限截断
+    let events = track.crossed(&player, 0., 1.0e9);
+    assert_eq!(events.len(), 3 + MAX_CROSSED_ITERATIONS as usize * 3 + 1);
+    assert_eq!(events.last().map(|event| event.payload), Some("start"));
+    let events = track.crossed(&player, 1.0e9, 0.);
+    assert_eq!(events.len(), MAX_CROSSED_ITERATIONS as usize * 3 + 3);
+
+    // 交替方向按整数迭代序号判断奇偶
+    player.direction = EPlaybackDirection::Alternate;
+    let names = track.crossed(&player, 0., 1.0e9).iter().rev().take(4).map(|event| event.payload).collect::<Vec<_>>();
+    assert_eq!(names, vec!["start", "start", "middle", "end"]);
+}
+
+#[test]
+fn test_blend() {
+    let amountcalc = AnimationAmountCalc::default();
+    let mut blender: AnimationBlender<f32> = AnimationBlender::new(EBlendMode::Normalized);
+    let walk = blender.add(FrameCurve::curve_easing(0.0, 10.0, 60, 60, EEasingMode::None), 1.0);
+    let run = blender.add(FrameCurve::curve_easing(0.0, 30.0, 60, 60, EEasingMode::None), 0.0);
+
+    assert_eq!(blender.sample(0.5, &amountcalc), Some(5.0));
+    blender.set_weight(run, 3.0);
+    assert_eq!(blender.sample(0.5, &amountcalc), Some(12.5));
+
+    // 定时淡入淡出, 线性进度
+    blender.cross_fade(walk, 1.0, AnimationAmountCalc::default());
+    blender.tick(0.5);
+    assert_eq!(blender.weight(walk), Some(1.0));
+    assert_eq!(blender.weight(run), Some(1.5));
+    blender.tick(0.75);
+    assert!(!blender.is_fading());
+    assert_eq!(blender.weight(run), Some(0.0));
+    assert_eq!(blender.sample(1.0, &amountcalc), Some(10.0));
+
+    // 缓动进度
+    blender.cross_fade(run, 2.0, AnimationAmountCalc::from_easing(EEasingMode::QuadIn));
+    blender.tick(1.0);
+    assert_eq!(blender.weight(run), Some(0.25));
+
+    assert_eq!(blend([(1.0f32, 0.5), (4.0, 2.0), (100.0, 0.0)], EBlendMode::Additive, f32::interpolate), Some(8.5));
+    assert_eq!(blend([(1.0f32, 0.0)], EBlendMode::Normalized, f32::interpolate), None);
+}
+
+#[cfg(feature = "nalgebra")]
+#[test]
+fn test_blend_slerp() {
+    let half_turn = UnitQuaternion::from_euler_angles(0., 0., std::f32::consts::PI).into_inner();
+    let mut blender = AnimationBlender::new_slerp();
+    blender.add(FrameCurve::curve_easing(Quaternion::identity(), Quaternion::identity(), 60, 60, EEasingMode::None), 1.0);
+    let mut rotation = FrameCurve::curve_frame_values(60);
+    rotation.curve_frame_values_frame(0, half_turn);
+    blender.add(rotation, 1.0);
+
+    let v = blender.sample(0.0, &AnimationAmountCalc::default()).unwrap();
+    let v = UnitQuaternion::new_normalize(v);
+    assert!((v.angle() - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+}
+
+#[test]
+fn test_additive_layers() {
+    // 行走: 手臂与身体位置
+    let mut walk: AnimationClip = AnimationClip::new(60);
+    walk.add_curve("body", "y", 0, FrameCurve::curve_easing(0.0f32, 10.0, 60, 60, EEasingMode::None)).unwrap();
+    walk.add_curve("body/arm", "angle", 0, FrameCurve::curve_easing(0.0f32, 100.0, 60, 60, EEasingMode::None)).unwrap();
+
+    // 呼吸: 以第 0 帧为参考的叠加剪辑
+    let mut breathe: AnimationClip = AnimationClip::new(60);
+    breathe.add_curve("body", "y", 0, FrameCurve::curve_easing(5.0f32, 2.0, 60, 60, EEasingMode::None)).unwrap();
+    let breathe = breathe.into_additive(0);
+    assert!(breathe.is_additive());
+    let delta = breathe.sample_pose(0.5, &AnimationAmountCalc::default());
+    assert_eq!(delta.get::<f32>("body", "y"), Some(&1.0));
+
+    // 瞄准: 仅覆盖手臂
+    let mut aim: AnimationClip = AnimationClip::new(60);
+    aim.add_curve("body", "y", 0, FrameCurve::curve_easing(-100.0f32, 0.0, 60, 60, EEasingMode::None)).unwrap();
+    aim.add_curve("body/arm", "angle", 0, FrameCurve::curve_easing(90.0f32, 0.0, 60, 60, EEasingMode::None)).unwrap();
+    let mut mask = AnimationMask::new();
+    mask.set("body/arm", 1.0);
+
+    let mut stack: AnimationLayerStack = AnimationLayerStack::new();
+    stack.push(AnimationLayer::new(walk));
+    let breathe = stack.push(AnimationLayer::new(breathe));
+    let aim = stack.push(AnimationLayer::new(aim));
+    stack.layer_mut(breathe).unwrap().weight = 0.5;
+    let layer = stack.layer_mut(aim).unwrap();
+    layer.weight = 0.5;
+    layer.mask = Some(mask);
+
+    stack.tick(0.5);
+    let pose = stack.evaluate();
+    assert_eq!(pose.get::<f32>("body", "y"), Some(&5.5));
+    assert_eq!(pose.get::<f32>("body/arm", "angle"), Some(&70.0));
+
+    let mut sampled = vec![];
+    pose.apply(&mut |target: &str, property: &str, _: KeyFrameDataType, value: &dyn Any| {
+        sampled.push((target.to_string(), property.to_string(), *value.downcast_ref::<f32>().unwrap()));
+    });
+    assert_eq!(sampled, vec![("body".to_string(), "y".to_string(), 5.5), ("body/arm".to_string(), "angle".to_string(), 70.0)]);
+}
+
+#[test]
+fn test_additive_delta_integer() {
+    // 无符号值的差量为负数时, 叠加后仍正确减少
+    let mut base: AnimationClip = AnimationClip::new(60);
+    base.add_curve("node", "alpha", 0, FrameCurve::curve_easing(200u8, 0, 60, 60, EEasingMode::None)).unwrap();
+    let mut fade: AnimationClip = AnimationClip::new(60);
+    fade.add_curve("node", "alpha", 0, FrameCurve::curve_easing(100u8, 0, 60, 60, EEasingMode::None)).unwrap();
+    let fade = fade.into_additive_with(pose_u8("node", "alpha", 140));
+
+    let mut stack: AnimationLayerStack = AnimationLayerStack::new();
+    stack.push(AnimationLayer::new(base));
+    let fade = stack.push(AnimationLayer::new(fade));
+    stack.layer_mut(fade).unwrap().weight = 0.5;
+    stack.tick(0.5);
+    assert_eq!(stack.evaluate().get::<u8>("node", "alpha"), Some(&180));
+}
+
+fn pose_u8(target: &str, property: &str, value: u8) -> AnimationPose {
+    let mut pose = AnimationPose::new();
+    pose.set(target, property, 0, value);
+    pose
+}
+
+fn clip_x(from: f32, to: f32, frames: FrameIndex) -> AnimationClip {
+    let mut clip = AnimationClip::new(60);
+    clip.add_curve("node", "x", 0, FrameCurve::curve_easing(from, to - from, frames, 60, EEasingMode::None)).unwrap();
+    clip
+}
+
+#[test]
+fn test_state_machine() {
+    let mut machine: AnimationStateMachine = AnimationStateMachine::new();
+    let idle = machine.add_state(AnimationState::new("idle", EAnimationMotion::Clip(clip_x(0., 0., 60))));
+    let walk = machine.add_state(AnimationState::new("walk", EAnimationMotion::Clip(clip_x(10., 20., 60))));
+    let mut jump = AnimationState::new("jump", EAnimationMotion::Clip(clip_x(100., 200., 30)));
+    jump.looping = false;
+    let jump = machine.add_state(jump);
+
+    machine.add_transition(idle, AnimationTransition::new(walk, 0.5).with_condition(EAnimationCondition::Greater("speed".to_string(), 0.1)));
+    machine.add_transition(walk, AnimationTransition::new(jump, 0.).with_condition(EAnimationCondition::Trigger("jump".to_string())));
+    machine.add_transition(jump, AnimationTransition::new(walk, 0.).with_exit_time(1.));
+    assert_eq!(machine.find_state("walk"), Some(walk));
+
+    machine.update(0.25);
+    assert_eq!(machine.current_state(), idle);
+    assert_eq!(machine.normalized_time(), 0.25);
+
+    machine.parameters_mut().set_float("speed", 1.);
+    machine.update(0.25);
+    assert_eq!(machine.current_state(), walk);
+    assert!(machine.is_transitioning());
+
+    // 淡入淡出过半: idle(0) 与 walk(12.5) 各一半
+    machine.update(0.25);
+    assert_eq!(machine.evaluate().get::<f32>("node", "x"), Some(&6.25));
+    machine.update(0.25);
+    assert!(!machine.is_transitioning());
+    assert_eq!(machine.evaluate().get::<f32>("node", "x"), Some(&15.));
+
+    machine.parameters_mut().set_trigger("jump");
+    machine.update(0.);
+    assert_eq!(machine.current_state(), jump);
+    assert!(!machine.parameters().trigger("jump"));
+
+    // 非循环状态播放完后按退出时间返回
+    machine.update(0.25);
+    assert_eq!(machine.evaluate().get::<f32>("node", "x"), Some(&150.));
+    machine.update(0.25);
+    assert_eq!(machine.current_state(), walk);
+}
+
+#[test]
+fn test_state_machine_easing() {
+    let mut machine: AnimationStateMachine = AnimationStateMachine::new();
+    let a = machine.add_state(AnimationState::new("a", EAnimationMotion::Clip(clip_x(0., 0., 60))));
+    let b = machine.add_state(AnimationState::new("b", EAnimationMotion::Clip(clip_x(100., 100., 60))));
+    machine.add_transition(a, AnimationTransition::new(b, 1.).with_condition(EAnimationCondition::True("go".to_string())).with_easing(EEasingMode::QuadIn));
+
+    machine.parameters_mut().set_bool("go", false);
+    machine.update(0.);
+    assert_eq!(machine.current_state(), a);
+    machine.parameters_mut().set_bool("go", true);
+    machine.update(0.);
+    machine.update(0.5);
+    assert_eq!(machine.evaluate().get::<f32>("node", "x"), Some(&25.));
+}
+
+#[test]
+fn test_blend_tree_weights() {
+    let mut tree: BlendTree1D = BlendTree1D::new("speed");
+    tree.add(3., EAnimationMotion::Clip(clip_x(0., 0., 60)));
+    tree.add(0., EAnimationMotion::Clip(clip_x(0., 0., 60)));
+    tree.add(1., EAnimationMotion::Clip(clip_x(0., 0., 60)));
+    assert_eq!(tree.thresholds().collect::<Vec<_>>(), vec![0., 1., 3.]);
+    assert_eq!(tree.weights(2.), vec![0., 0.5, 0.5]);
+    assert_eq!(tree.weights(-1.), vec![1., 0., 0.]);
+    assert_eq!(tree.weights(5.), vec![0., 0., 1.]);
+
+    let mut tree: BlendTree2D = BlendTree2D::new("x", "y", EBlendTree2DMode::FreeformCartesian);
+    for position in [[0., 0.], [1., 0.], [0., 1.], [1., 1.]] {
+        tree.add(position, EAnimationMotion::Clip(clip_x(0., 0., 60)));
+    }
+    assert_eq!(tree.weights([0.5, 0.5]), vec![0.25; 4]);
+    assert_eq!(tree.weights([1., 0.]), vec![0., 1., 0., 0.]);
+
+    // 原点待机 + 四方向
+    let mut tree: BlendTree2D = BlendTree2D::new("x", "y", EBlendTree2DMode::FreeformDirectional);
+    for position in [[0., 0.], [0., 1.], [1., 0.], [-1., 0.], [0., -1.]] {
+        tree.add(position, EAnimationMotion::Clip(clip_x(0., 0., 60)));
+    }
+    assert_eq!(tree.weights([0., 1.]), vec![0., 1., 0., 0., 0.]);
+    assert_eq!(tree.weights([0., 0.5]), vec![0.5, 0.5, 0., 0., 0.]);
+    let weights = tree.weights([0.5f32.sqrt(), 0.5f32.sqrt()]);
+    assert!((weights[1] - 0.5).abs() < 1e-5 && (weights[2] - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn test_blend_tree_sync() {
+    // 时长不同的动作按归一化时间同步
+    let mut tree: BlendTree1D = BlendTree1D::new("speed");
+    tree.add(0., EAnimationMotion::Clip(clip_x(0., 10., 60)));
+    tree.add(1., EAnimationMotion::Clip(clip_x(0., 100., 120)));
+
+    let mut machine: AnimationStateMachine = AnimationStateMachine::new();
+    machine.add_state(AnimationState::new("move", EAnimationMotion::BlendTree1D(tree)));
+    machine.parameters_mut().set_float("speed", 0.5);
+    assert_eq!(machine.state(0).unwrap().motion.duration(machine.parameters()), 1.5);
+
+    machine.update(0.75);
+    assert_eq!(machine.normalized_time(), 0.5);
+    assert_eq!(machine.evaluate().get::<f32>("node", "x"), Some(&27.5));
+}
+
+#[test]
+fn test_timeline() {
+    let linear = |from: f32, to: f32, frames: FrameIndex| FrameCurve::curve_easing(from, to - from, frames, 60, EEasingMode::None);
+
+    // 移动, 然后淡出, 同时缩放
+    let mut timeline: Timeline = Timeline::new();
+    timeline
+        .then(TimelineItem::curve("node", "x", 0, linear(0., 10., 60)))
+        .then(TimelineItem::curve("node", "alpha", 0, linear(1., 0., 30)))
+        .with(TimelineItem::curve("node", "scale", 0, linear(1., 2., 60)));
+    assert_eq!(timeline.duration(), 2.0);
+
+    let pose = timeline.evaluate(0.5);
+    assert_eq!(pose.get::<f32>("node", "x"), Some(&5.));
+    assert_eq!(pose.get::<f32>("node", "alpha"), Some(&1.));
+    assert_eq!(pose.get::<f32>("node", "scale"), Some(&1.));
+
+    let pose = timeline.evaluate(1.25);
+    assert_eq!(pose.get::<f32>("node", "x"), Some(&10.));
+    assert_eq!(pose.get::<f32>("node", "alpha"), Some(&0.5));
+    assert_eq!(pose.get::<f32>("node", "scale"), Some(&1.25));
+
+    // 交错编排的子时间轴嵌套在 1 秒之后, 同一轨道的后一项接管
+    let stagger = Timeline::stagger((0..3).map(|i| TimelineItem::curve(&format!("item{}", i), "y", 0, linear(0., 1., 30))), 0.25);
+    assert_eq!(stagger.duration(), 1.0);
+    timeline.insert(1.0, TimelineItem::timeline(stagger));
+    timeline.insert(1.5, TimelineItem::curve("node", "x", 0, linear(10., 0., 30)));
+    assert_eq!(timeline.duration(), 2.0);
+
+    let pose = timeline.evaluate(1.5);
+    assert_eq!(pose.get::<f32>("item0", "y"), Some(&1.));
+    assert_eq!(pose.get::<f32>("item1", "y"), Some(&0.5));
+    assert_eq!(pose.get::<f32>("item2", "y"), Some(&0.));
+    assert_eq!(pose.get::<f32>("node", "x"), Some(&10.));
+    assert_eq!(timeline.evaluate(1.75).get::<f32>("node", "x"), Some(&5.));
+
+    let parallel = Timeline::parallel([TimelineItem::curve("a", "v", 0, linear(0., 1., 60)), TimelineItem::curve("b", "v", 0, linear(0., 1., 120))]);
+    assert_eq!(parallel.duration(), 2.0);
+    assert_eq!(parallel.evaluate(1.5).get::<f32>("a", "v"), Some(&1.));
+}
+
+#[test]
+fn test_timeline_tween() {
+    // 补间项: 延迟 0.5 秒, 往返重复一次
+    let tween: Tween<f32> = Tween::new(0., 10., 1.0).with_delay(0.5).with_repeat(1).with_yoyo(true);
+    let mut timeline: Timeline = Timeline::new();
+    timeline
+        .then(TimelineItem::curve("node", "x", 0, FrameCurve::curve_easing(5.0f32, 0., 60, 60, EEasingMode::None)))
+        .then(TimelineItem::tween("node", "x", 0, tween))
+        .with(TimelineItem::tween("node", "alpha", 0, Tween::new(1.0f32, 0., 0.5)));
+    assert_eq!(timeline.duration(), 3.5);
+
+    assert_eq!(timeline.evaluate(0.5).get::<f32>("node", "x"), Some(&5.));
+    // 第一项开始前取起始值
+    assert_eq!(timeline.evaluate(0.5).get::<f32>("node", "alpha"), Some(&1.));
+    assert_eq!(timeline.evaluate(1.25).get::<f32>("node", "x"), Some(&0.));
+    assert_eq!(timeline.evaluate(1.25).get::<f32>("node", "alpha"), Some(&0.5));
+    assert_eq!(timeline.evaluate(2.0).get::<f32>("node", "x"), Some(&5.));
+    assert_eq!(timeline.evaluate(3.0).get::<f32>("node", "x"), Some(&5.));
+    assert_eq!(timeline.evaluate(10.0).get::<f32>("node", "x"), Some(&0.));
+
+    // 插入顺序与开始时间无关, 嵌套时间轴中的补间同样参与排序
+    let mut timeline: Timeline = Timeline::new();
+    timeline.insert(1.0, TimelineItem::curve("node", "x", 0, FrameCurve::curve_easing(7.0f32, 0., 60, 60, EEasingMode::None)));
+    timeline.insert(0.0, TimelineItem::timeline(Timeline::parallel([TimelineItem::tween("node", "x", 0, Tween::new(0.0f32, 2., 1.0))])));
+    assert_eq!(timeline.evaluate(0.5).get::<f32>("node", "x"), Some(&1.));
+    assert_eq!(timeline.evaluate(1.5).get::<f32>("node", "x"), Some(&7.));
+}
+
+#[test]
+fn test_tween() {
+    let completed = std::rc::Rc::new(std::cell::Cell::new(0));
+    let counter = completed.clone();
+    let mut tween: Tween<f32> = Tween::new(0., 10., 1.0)
+        .with_delay(0.5)
+        .with_repeat(1)
+        .with_yoyo(true)
+        .on_complete(move |value| { assert_eq!(*value, 0.); counter.set(counter.get() + 1); });
+    assert_eq!(tween.total_duration(), Some(2.5));
+
+    assert_eq!(tween.tick(0.25), 0.);
+    assert_eq!(tween.tick(0.75), 5.);
+    assert_eq!(tween.tick(1.0), 5.);
+    assert!(!tween.is_complete());
+    assert_eq!(tween.tick(1.0), 0.);
+    assert_eq!(tween.tick(1.0), 0.);
+    assert!(tween.is_complete());
+    assert_eq!(completed.get(), 1);
+
+    tween.restart();
+    assert!(!tween.is_complete());
+}
+
+#[test]
+fn test_tween_retarget() {
+    let dt = 0.01;
+    let mut tween: Tween<f32> = Tween::new(0., 10., 1.0);
+    let start = tween.tick(0.5 - dt);
+    let current = tween.tick(dt);
+    let before = (current - start) / dt;
+
+    // 从当前值出发, 初速度保持不变
+    tween.retarget(-10., 2.0);
+    assert!((tween.value() - current).abs() < 1e-4);
+    let after = (tween.tick(dt) - current) / dt;
+    assert!((before - after).abs() < 0.5, "{} {}", before, after);
+
+    assert!(!tween.is_complete());
+    assert_eq!(tween.tick(2.0), -10.);
+    assert!(tween.is_complete());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_clip_par_sample() {
+    let mut clip: AnimationClip = AnimationClip::new(60);
+    for index in 0..3000 {
+        let target = format!("node{}", index);
+        clip.add_curve(&target, "alpha", 0, FrameCurve::curve_easing(index as f32, 60., 60, 60, EEasingMode::None)).unwrap();
+        clip.add_curve(&target, "scale", 1, FrameCurve::curve_easing([1.0f32, 1.0], [2.0, 3.0], 60, 60, EEasingMode::None)).unwrap();
+    }
+
+    let mut output = vec![0.0f32; 3000];
+    clip.par_sample_type(0.5, &AnimationAmountCalc::default(), &mut output).unwrap();
+    for (index, value) in output.iter().enumerate() {
+        assert_eq!(*value, clip.curve::<f32>(&format!("node{}", index), "alpha").unwrap().interple(0.5, &AnimationAmountCalc::default()));
+    }
+    let mut scales = vec![[0.0f32; 2]; 3000];
+    clip.par_sample_type(0.5, &AnimationAmountCalc::default(), &mut scales).unwrap();
+    let scale = clip.curve::<[f32; 2]>("node0", "scale").unwrap().interple(0.5, &AnimationAmountCalc::default());
+    assert!((scale[1] - 2.5).abs() < 1e-5 && scales.iter().all(|value| *value == scale));
+    assert!(matches!(clip.par_sample_type(0.5, &AnimationAmountCalc::default(), &mut output[1..]), Err(ErrorCurve::ChannelCountMismatch)));
+
+    // 所有通道并行采样为姿态, 与串行采样一致
+    let pose = clip.par_sample_pose(0.5, &AnimationAmountCalc::default());
+    assert_eq!(pose.len(), clip.channels().len());
+    assert_eq!(pose.get::<f32>("node7", "alpha"), clip.sample_pose(0.5, &AnimationAmountCalc::default()).get::<f32>("node7", "alpha"));
+    assert_eq!(pose.get::<[f32; 2]>("node7", "scale"), Some(&scale));
+
+    // 剪辑可跨线程共享
+    fn shared<S: Send + Sync>(_: &S) {}
+    shared(&clip);
+
+    // 叠加剪辑输出差量
+    let clip = clip.into_additive(0);
+    clip.par_sample_type(0.5, &AnimationAmountCalc::default(), &mut output).unwrap();
+    assert!(output.iter().all(|value| (value - 30.).abs() < 1e-3));
+    assert!(clip.par_sample_pose(0.5, &AnimationAmountCalc::default()).get::<f32>("node7", "alpha").is_some_and(|value| (value - 30.).abs() < 1e-3));
+}
+
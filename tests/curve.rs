@@ -6,7 +6,8 @@ mod test_frame {
 
     use std::ops::Add;
 
-    use pi_curves::{curve::{frame::{FrameDataValue, KeyFrameCurveValue, FrameValueScale}, frame_curve::FrameCurve, FrameIndex}, easing::{EEasingMode, function::sine_in_out}, amount::AnimationAmountCalc, steps::EStepMode};
+    use pi_curves::{curve::{bake::EBakeMode, bank::{CurveBank, ECurveBankMode}, binary::BINARY_VERSION, compress::{CompressedCurve, EQuantization}, fit::{fit_cubic_spline, fit_weighted_bezier}, reduce::ReduceTolerance, frame::{FrameDataValue, KeyFrameCurveValue, FrameValueScale}, frame_curve::FrameCurve, ErrorCurve, FrameIndex}, easing::{EEasingMode, function::{easing_call, sine_in_out}, slice::ease_slice, lut::{EasingLut, ELutInterpolation}}, hermite::{hermite, hermite_slice}, amount::AnimationAmountCalc, steps::EStepMode, AmountParam, EAmountMode};
+    use std::sync::Arc;
     use test::Bencher;
     
     #[test]
@@ -39,6 +40,488 @@ mod test_frame {
         // });
     }   
 
+    #[test]
+    fn test_f64_precision() {
+        // 60000 帧 (1000 秒) 的时间轴, 取值 = 帧序号
+        let mut curve: FrameCurve<f64, f64> = FrameCurve::curve_frame_values(60);
+        curve.curve_frame_values_frame(0, 0.0);
+        curve.curve_frame_values_frame(60000, 60000.0);
+
+        let time = 999.999_99f64;
+        let v = curve.interple(time, &AnimationAmountCalc::default());
+        assert!((v - time * 60.).abs() < 1e-6);
+
+        let mut curve32: FrameCurve<f32> = FrameCurve::curve_frame_values(60);
+        curve32.curve_frame_values_frame(0, 0.0);
+        curve32.curve_frame_values_frame(60000, 60000.0);
+        let v32 = curve32.interple(time as f32, &AnimationAmountCalc::default());
+        assert!((v32 as f64 - time * 60.).abs() > 1e-4);
+
+        let curve: FrameCurve<f64, f64> = FrameCurve::curve_easing(0.0, 1.0, 60, 60, EEasingMode::SineInOut);
+        let v = curve.interple(0.5, &AnimationAmountCalc::from_easing(EEasingMode::None));
+        assert!((v - 0.5).abs() < 1e-12);
+
+        let mut curve: FrameCurve<f64, f64> = FrameCurve::curve_minmax_curve(0.0, 1.0, 60);
+        curve.curve_minmax_curve_frame(0, 0.0, 0.0, 0.0);
+        curve.curve_minmax_curve_frame(60, 1.0, 0.0, 0.0);
+        let v = curve.interple(0.5, &AnimationAmountCalc::from_cubic_bezier(0.25, 0.25, 0.75, 0.75));
+        assert!((v - 0.5).abs() < 1e-9);
+
+        let mut curve: FrameCurve<[u16; 2], f64> = FrameCurve::curve_cubic_spline(60);
+        curve.curve_cubic_splice_frame(0, [0, 100], [0, 0], [0, 0]);
+        curve.curve_cubic_splice_frame(60, [1000, 0], [0, 0], [0, 0]);
+        assert_eq!(curve.interple(0.5, &AnimationAmountCalc::default()), [500, 50]);
+    }
+
+    const EASING_MODES: [EEasingMode; 31] = [
+        EEasingMode::None,
+        EEasingMode::BackIn, EEasingMode::BackOut, EEasingMode::BackInOut,
+        EEasingMode::CircleIn, EEasingMode::CircleOut, EEasingMode::CircleInOut,
+        EEasingMode::CubicIn, EEasingMode::CubicOut, EEasingMode::CubicInOut,
+        EEasingMode::SineIn, EEasingMode::SineOut, EEasingMode::SineInOut,
+        EEasingMode::QuadIn, EEasingMode::QuadOut, EEasingMode::QuadInOut,
+        EEasingMode::QuartIn, EEasingMode::QuartOut, EEasingMode::QuartInOut,
+        EEasingMode::QuintIn, EEasingMode::QuintOut, EEasingMode::QuintInOut,
+        EEasingMode::ExpoIn, EEasingMode::ExpoOut, EEasingMode::ExpoInOut,
+        EEasingMode::ElasticIn, EEasingMode::ElasticOut, EEasingMode::ElasticInOut,
+        EEasingMode::BounceIn, EEasingMode::BounceOut, EEasingMode::BounceInOut,
+    ];
+
+    #[test]
+    fn test_ease_slice() {
+        // 长度不是通道宽度的整数倍, 覆盖尾部计算
+        let amounts: Vec<f32> = (0..=101).map(|i| i as f32 / 101.).collect();
+        for mode in EASING_MODES {
+            let mut values = amounts.clone();
+            ease_slice(mode, &mut values);
+            for (amount, value) in amounts.iter().zip(values.iter()) {
+                let expect = easing_call(*amount, &mode);
+                assert!((value - expect).abs() < 1e-5, "{:?} {} {} {}", mode, amount, value, expect);
+            }
+        }
+
+        let mut values = amounts.clone();
+        hermite_slice(1.0, 2.0, 3.0, -1.0, &mut values);
+        for (amount, value) in amounts.iter().zip(values.iter()) {
+            assert!((value - hermite(1.0f32, 2.0, 3.0, -1.0, *amount)).abs() < 1e-5);
+        }
+        // 同一进度的结果与其在切片中的位置无关
+        for offset in 0..8 {
+            let mut values = amounts[offset..].to_vec();
+            hermite_slice(1.0, 2.0, 3.0, -1.0, &mut values);
+            let mut tail = amounts[amounts.len() - 5..].to_vec();
+            hermite_slice(1.0, 2.0, 3.0, -1.0, &mut tail);
+            for (index, value) in values.iter().enumerate() {
+                let mut single = [amounts[offset + index]];
+                hermite_slice(1.0, 2.0, 3.0, -1.0, &mut single);
+                assert_eq!(value.to_bits(), single[0].to_bits(), "{} {}", offset, index);
+            }
+            assert_eq!(&values[values.len() - 5..], &tail[..]);
+        }
+
+        // 超出 [0, 1] 的进度: Circle 类在尾部同样取 0 开方, 结果与切片长度无关
+        for mode in [EEasingMode::CircleIn, EEasingMode::CircleOut, EEasingMode::CircleInOut] {
+            let mut chunk = [1.5f32; 8];
+            ease_slice(mode, &mut chunk);
+            for len in [1, 9, 13] {
+                let mut values = vec![1.5f32; len];
+                ease_slice(mode, &mut values);
+                assert!(values.iter().all(|value| value.to_bits() == chunk[0].to_bits()), "{:?} {}", mode, len);
+            }
+        }
+
+        let mut values: Vec<f32> = vec![];
+        ease_slice(EEasingMode::BounceInOut, &mut values);
+        hermite_slice(0., 1., 1., 0., &mut values);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel() {
+        use pi_curves::curve::parallel::{par_interple, par_interple_times};
+
+        let curves: Vec<FrameCurve<f32, f32>> = (0..5000).map(|index| {
+            let mut curve = FrameCurve::curve_frame_values(60);
+            curve.curve_frame_values_frame(0, index as f32);
+            curve.curve_frame_values_frame(60, index as f32 * 2.);
+            curve
+        }).collect();
+        let amountcalc = AnimationAmountCalc::from_easing(EEasingMode::QuadInOut);
+
+        let mut output = vec![0.0f32; curves.len()];
+        par_interple(&curves, 0.3, &amountcalc, &mut output).unwrap();
+        for (curve, value) in curves.iter().zip(output.iter()) {
+            assert_eq!(*value, curve.interple(0.3, &amountcalc));
+        }
+
+        let times: Vec<f32> = (0..curves.len()).map(|index| (index % 61) as f32 / 60.).collect();
+        par_interple_times(&curves, &times, &amountcalc, &mut output).unwrap();
+        for ((curve, time), value) in curves.iter().zip(times.iter()).zip(output.iter()) {
+            assert_eq!(*value, curve.interple(*time, &amountcalc));
+        }
+
+        curves[7].par_interple_many(&times, &amountcalc, &mut output).unwrap();
+        for (time, value) in times.iter().zip(output.iter()) {
+            assert_eq!(*value, curves[7].interple(*time, &amountcalc));
+        }
+
+        assert!(matches!(par_interple(&curves, 0.3, &amountcalc, &mut output[1..]), Err(ErrorCurve::ChannelCountMismatch)));
+        assert!(matches!(par_interple_times(&curves, &times[1..], &amountcalc, &mut output), Err(ErrorCurve::ChannelCountMismatch)));
+    }
+
+    #[test]
+    fn test_easing_lut() {
+        let sources = [
+            AnimationAmountCalc::from_easing(EEasingMode::ElasticInOut),
+            AnimationAmountCalc::from_easing(EEasingMode::BounceOut),
+            AnimationAmountCalc::from_easing(EEasingMode::ExpoInOut),
+            AnimationAmountCalc::from_cubic_bezier(0.1, 0.7, 0.2, 1.0),
+        ];
+        for source in sources.iter() {
+            let linear = EasingLut::new(source, 256, ELutInterpolation::Linear);
+            let cubic = EasingLut::new(source, 256, ELutInterpolation::Cubic);
+            assert_eq!(linear.resolution(), 256);
+            assert!(linear.error_bound() < 0.01, "{:?} {}", source.mode(), linear.error_bound());
+            assert!(cubic.error_bound() < 0.01, "{:?} {}", source.mode(), cubic.error_bound());
+            for lut in [&linear, &cubic] {
+                assert_eq!(lut.calc(0.), source.calc(0.));
+                assert_eq!(lut.calc(1.), source.calc(1.));
+                assert_eq!(lut.calc(-1.), lut.calc(0.));
+                for i in 0..=1000 {
+                    let amount = i as f32 / 1000.;
+                    assert!((lut.calc(amount) - source.calc(amount)).abs() <= lut.error_bound() * 1.5 + 1e-6);
+                }
+            }
+        }
+
+        // 平滑曲线上三次插值误差更小
+        let bezier = AnimationAmountCalc::from_cubic_bezier(0.25, 0.1, 0.25, 1.0);
+        let linear = EasingLut::new(&bezier, 64, ELutInterpolation::Linear);
+        let cubic = EasingLut::new(&bezier, 64, ELutInterpolation::Cubic);
+        assert!(cubic.error_bound() < linear.error_bound());
+        assert!(EasingLut::new(&bezier, 16, ELutInterpolation::Linear).error_bound() > linear.error_bound());
+        assert_eq!(EasingLut::new(&bezier, 0, ELutInterpolation::Linear).resolution(), 1);
+
+        // 作为进度计算模式
+        let cubic = Arc::new(cubic);
+        let amountcalc = AnimationAmountCalc::from_lut(cubic.clone());
+        assert!(matches!(amountcalc.mode(), EAmountMode::Lut(ELutInterpolation::Cubic)));
+        assert!(amountcalc.lut().is_some());
+        let curve = FrameCurve::curve_easing(0.0f32, 10.0, 60, 60, EEasingMode::None);
+        let expect = curve.interple(0.4, &bezier);
+        assert!((curve.interple(0.4, &amountcalc) - expect).abs() <= amountcalc.lut().unwrap().error_bound() * 10. + 1e-5);
+
+        // 二进制记录保存源进度计算
+        let mut bytes = vec![];
+        amountcalc.write_binary(&mut bytes);
+        let (result, _) = AnimationAmountCalc::<f32>::read_binary(&bytes).unwrap();
+        assert!(matches!(result.mode(), EAmountMode::CubicBezier));
+        assert_eq!(result.calc(0.4), bezier.calc(0.4));
+
+        // 查找表由进度计算共享, 全部释放后回收
+        let shared = amountcalc.clone();
+        assert_eq!(Arc::strong_count(&cubic), 3);
+        let table = Arc::downgrade(&cubic);
+        drop((cubic, amountcalc, shared));
+        assert!(table.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_bake() {
+        let distance = |a: &f32, b: &f32| (a - b).abs();
+
+        // 30 帧的缓动曲线烘焙到 60 帧, 关键帧处与源曲线一致
+        let curve: FrameCurve<f32> = FrameCurve::curve_easing(0.0, 1.0, 30, 30, EEasingMode::BounceOut);
+        let amountcalc = AnimationAmountCalc::default();
+        let (baked, error) = curve.bake_with_error(60, EBakeMode::FrameValues, &amountcalc, distance).unwrap();
+        assert_eq!(baked.frames.len(), 61);
+        assert_eq!((baked.min_frame, baked.max_frame), (0, 60));
+        for frame in [0, 17, 45, 60] {
+            let time = frame as f32 / 60.;
+            assert!((baked.interple(time, &amountcalc) - curve.interple(time, &amountcalc)).abs() < 1e-5);
+        }
+        assert!(error.max > 0. && error.max < 0.1);
+        assert!(error.mean <= error.max);
+
+        // 帧率越高误差越小
+        let (_, fine) = curve.bake_with_error(240, EBakeMode::FrameValues, &amountcalc, distance).unwrap();
+        assert!(fine.max < error.max);
+
+        // 进度计算烘焙进结果, 时长不是整数帧时末帧取结束值
+        let mut curve: FrameCurve<f32> = FrameCurve::curve_frame_values(30);
+        curve.curve_frame_values_frame(0, 0.0);
+        curve.curve_frame_values_frame(10, 1.0);
+        let steps = AnimationAmountCalc::from_steps(2, EStepMode::JumpEnd);
+        let baked = curve.bake(20, EBakeMode::FrameValuesStep, &steps).unwrap();
+        assert_eq!(baked.frames, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(baked.values, vec![0., 0., 0., 0., 0.5, 0.5, 0.5, 1.0]);
+        assert_eq!(baked.interple(0.2, &amountcalc), 0.5);
+
+        let empty: FrameCurve<f32> = FrameCurve::curve_frame_values(30);
+        assert!(empty.bake(60, EBakeMode::FrameValues, &amountcalc).unwrap().frames.is_empty());
+
+        // 结束帧超出 FrameIndex 范围时返回错误, 而非截断
+        let mut long: FrameCurve<f32> = FrameCurve::curve_frame_values(30);
+        long.curve_frame_values_frame(0, 0.0);
+        long.curve_frame_values_frame(FrameIndex::MAX, 1.0);
+        assert!(matches!(long.bake(60, EBakeMode::FrameValues, &amountcalc), Err(ErrorCurve::FrameIndexOverflow)));
+        assert!(matches!(long.bake_with_error(60, EBakeMode::FrameValues, &amountcalc, distance), Err(ErrorCurve::FrameIndexOverflow)));
+        assert_eq!(long.bake(30, EBakeMode::FrameValues, &amountcalc).unwrap().max_frame, FrameIndex::MAX);
+    }
+
+    #[test]
+    fn test_reduce() {
+        let distance = |a: &f32, b: &f32| (a - b).abs();
+        let amountcalc = AnimationAmountCalc::default();
+
+        // 直线上的关键帧全部冗余
+        let mut line: FrameCurve<f32> = FrameCurve::curve_frame_values(60);
+        for frame in 0..=60 {
+            line.curve_frame_values_frame(frame, frame as f32 * 0.5);
+        }
+        let (reduced, stats) = line.reduce_frame_values(ReduceTolerance::absolute(1e-4), distance);
+        assert_eq!(reduced.frames, vec![0, 60]);
+        assert_eq!((stats.keys_before, stats.keys_after), (61, 2));
+        assert_eq!(stats.compression(), 30.5);
+
+        // 逐帧的平滑曲线, 各精简结果在源关键帧处的误差不超限
+        let source: FrameCurve<f32> = FrameCurve::curve_easing(0.0, 2.0, 120, 60, EEasingMode::SineInOut);
+        let dense = source.bake(60, EBakeMode::FrameValues, &amountcalc).unwrap();
+        let tolerance = ReduceTolerance::relative(0.005);
+        let (linear, linear_stats) = dense.reduce_frame_values(tolerance, distance);
+        let (spline, spline_stats) = dense.reduce_cubic_spline(tolerance, distance);
+        let (minmax, minmax_stats) = dense.reduce_minmax_curve(tolerance);
+        for (curve, stats) in [(&linear, linear_stats), (&spline, spline_stats), (&minmax, minmax_stats)] {
+            assert!(stats.keys_after < stats.keys_before / 4, "{:?}", stats);
+            assert!(stats.max_error <= 0.01);
+            for (frame, value) in dense.frames.iter().zip(dense.values.iter()) {
+                assert!((curve.interple(*frame as f32 / 60., &amountcalc) - value).abs() <= 0.01 + 1e-5);
+            }
+        }
+        // 三次样条比线性需要更少的关键帧
+        assert!(spline_stats.keys_after < linear_stats.keys_after);
+    }
+
+    #[test]
+    fn test_fit() {
+        let distance = |a: &f32, b: &f32| (a - b).abs();
+        let amountcalc = AnimationAmountCalc::default();
+
+        // 三次多项式只需首尾两个关键帧
+        let cubic: Vec<(FrameIndex, f32)> = (0..=30).map(|frame| { let t = frame as f32 / 30.; (frame, t * t * t - t) }).collect();
+        let (curve, stats) = fit_cubic_spline(&cubic, 30, ReduceTolerance::absolute(1e-4), distance);
+        assert_eq!(curve.frames, vec![0, 30]);
+        assert!(stats.max_error < 1e-4);
+
+        // 阻尼弹簧的模拟采样
+        let spring: Vec<(FrameIndex, f32)> = (0..=120).map(|frame| { let t = frame as f32 / 60.; (frame, (-3. * t).exp() * (10. * t).cos()) }).collect();
+        let tolerance = ReduceTolerance::absolute(0.005);
+        let (spline, spline_stats) = fit_cubic_spline(&spring, 60, tolerance, distance);
+        let (bezier, bezier_stats) = fit_weighted_bezier(&spring, 60, tolerance);
+        assert!(spline_stats.keys_after < 20, "{:?}", spline_stats);
+        assert!(bezier_stats.keys_after <= spline_stats.keys_after, "{:?}", bezier_stats);
+        for (frame, value) in spring.iter() {
+            let time = *frame as f32 / 60.;
+            assert!((spline.interple(time, &amountcalc) - value).abs() <= 0.005 + 1e-5);
+            assert!((bezier.interple(time) - value).abs() <= 0.005 + 1e-5);
+        }
+        assert_eq!(bezier.keys[0].value, 1.0);
+        assert_eq!(bezier.interple(5.0), spring[120].1);
+    }
+
+    #[test]
+    fn test_compress() {
+        let amountcalc = AnimationAmountCalc::default();
+
+        // 帧间隔不等, 含超过 255 帧的间隔
+        let mut curve: FrameCurve<[f32; 3]> = FrameCurve::curve_frame_values(30);
+        let frames: Vec<FrameIndex> = (0..100).map(|index| index * 3).chain([600, 1000]).collect();
+        for (index, frame) in frames.iter().enumerate() {
+            let t = index as f32 * 0.1;
+            curve.curve_frame_values_frame(*frame, [t.sin() * 10., t.cos(), 5.]);
+        }
+        // 原始数据每个关键帧 2 + 12 字节
+        let size = frames.len() * 14;
+        for (quantization, tolerance, max_size) in [(EQuantization::Bits16, 20. / 65535., size * 2 / 3), (EQuantization::Bits8, 20. / 255., size / 2)] {
+            let compressed = CompressedCurve::new(&curve, quantization).unwrap();
+            assert_eq!(compressed.len(), frames.len());
+            assert!(compressed.memory_size() < max_size);
+            for (index, frame) in frames.iter().enumerate() {
+                assert_eq!(compressed.frame(index), *frame);
+            }
+            for time in [0., 0.05, 3.3, 9.95, 15., 25., 40.] {
+                let (a, b) = (compressed.interple(time, &amountcalc), curve.interple(time, &amountcalc));
+                assert!(a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() <= tolerance), "{} {:?} {:?}", time, a, b);
+            }
+        }
+
+        let empty: FrameCurve<f32> = FrameCurve::curve_frame_values(30);
+        assert!(CompressedCurve::new(&empty, EQuantization::Bits8).is_none());
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_compress_rotation() {
+        use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+        use pi_curves::curve::compress::CompressedRotationCurve;
+
+        let amountcalc = AnimationAmountCalc::default();
+        let mut curve: FrameCurve<Quaternion<f32>> = FrameCurve::curve_frame_values(30);
+        for frame in 0..=60 {
+            let t = frame as f32 * 0.1;
+            let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), t) * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -2. * t);
+            curve.curve_frame_values_frame(frame, *rotation.quaternion());
+        }
+        let compressed: CompressedRotationCurve<Quaternion<f32>> = CompressedRotationCurve::new(&curve, EQuantization::Bits16).unwrap();
+        assert_eq!(compressed.memory_size(), 4 * 2 + 60 + 61 * 6 + 16);
+        for index in 0..=60 {
+            let (a, b) = (compressed.value(index), curve.values[index]);
+            assert!(a.dot(&b).abs() > 1. - 1e-6, "{} {:?} {:?}", index, a, b);
+        }
+        let (a, b) = (compressed.interple(1.01, &amountcalc), curve.values[30]);
+        assert!(a.dot(&b).abs() > 0.999);
+    }
+
+    #[test]
+    fn test_bank() {
+        let amountcalc = AnimationAmountCalc::default();
+        let mut curves: Vec<FrameCurve<f32>> = Vec::new();
+        let mut splines: Vec<FrameCurve<f32>> = Vec::new();
+        for channel in 0..5 {
+            let mut curve = FrameCurve::curve_frame_values(30);
+            let mut spline = FrameCurve::curve_cubic_spline(30);
+            for (index, frame) in [0, 10, 25, 30].iter().enumerate() {
+                let value = (channel * 4 + index) as f32;
+                curve.curve_frame_values_frame(*frame, value);
+                spline.curve_cubic_splice_frame(*frame, value, value * 0.5, -value);
+            }
+            curves.push(curve);
+            splines.push(spline);
+        }
+
+        let mut result = vec![0.; 5];
+        for curves in [&curves, &splines] {
+            let bank = CurveBank::from_curves(&curves.iter().collect::<Vec<_>>()).unwrap();
+            assert_eq!(bank.channels(), 5);
+            for time in [-1., 0., 0.2, 1. / 3., 0.9, 2.] {
+                bank.evaluate_all(time, &mut result).unwrap();
+                for (value, curve) in result.iter().zip(curves.iter()) {
+                    assert!((value - curve.interple(time, &amountcalc)).abs() < 1e-5, "{} {} {}", time, value, curve.interple(time, &amountcalc));
+                }
+            }
+        }
+
+        let mut bank: CurveBank<f32> = CurveBank::new(30, ECurveBankMode::Step, 2);
+        assert!(matches!(bank.evaluate_all(0.6, &mut result[..2]), Err(ErrorCurve::KeyFramesEmpty)));
+        bank.add_key(30, &[1., 10.]).unwrap();
+        bank.add_key(0, &[0., 20.]).unwrap();
+        assert!(matches!(bank.add_key(15, &[0.]), Err(ErrorCurve::ChannelCountMismatch)));
+        assert_eq!(bank.frames(), &[0, 30]);
+        bank.evaluate_all(0.6, &mut result[..2]).unwrap();
+        assert_eq!(&result[..2], &[1., 10.]);
+        // 输出长度与通道数量不一致时不写入
+        assert!(matches!(bank.evaluate_all(0.6, &mut result), Err(ErrorCurve::ChannelCountMismatch)));
+        assert!(matches!(bank.evaluate_all(0.6, &mut result[..1]), Err(ErrorCurve::ChannelCountMismatch)));
+
+        curves[1].curve_frame_values_frame(40, 0.);
+        assert!(matches!(CurveBank::from_curves(&curves.iter().collect::<Vec<_>>()), Err(ErrorCurve::KeyFramesMismatch)));
+    }
+
+    /// 线性同余伪随机数, [0, 1)
+    struct Random(u64);
+    impl Random {
+        fn next(&mut self) -> f32 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 40) as f32 / (1u64 << 24) as f32
+        }
+        fn frame(&mut self) -> FrameIndex {
+            (self.next() * 600.) as FrameIndex
+        }
+        fn value(&mut self) -> [f32; 2] {
+            [self.next() * 10. - 5., self.next()]
+        }
+    }
+
+    fn random_curve(random: &mut Random, kind: usize) -> FrameCurve<[f32; 2]> {
+        let easings = [EEasingMode::None, EEasingMode::BackInOut, EEasingMode::SineOut, EEasingMode::BounceInOut];
+        let mut curve = match kind {
+            0 => FrameCurve::curve_frame_values(30),
+            1 => FrameCurve::curve_frame_values_step(60),
+            2 => return FrameCurve::curve_easing(random.value(), random.value(), 1 + (random.next() * 100.) as FrameIndex, 30, easings[(random.next() * 4.) as usize]),
+            3 => FrameCurve::curve_minmax_curve(random.value(), random.value(), 30),
+            4 => return FrameCurve::curve_cubic_bezier(random.value(), random.value(), 1 + (random.next() * 100.) as FrameIndex, 24, random.next(), random.next(), random.next(), random.next()),
+            _ => FrameCurve::curve_cubic_spline(30),
+        };
+        for _ in 0..(random.next() * 20.) as usize {
+            let frame = random.frame();
+            match kind {
+                0 | 1 => curve.curve_frame_values_frame(frame, random.value()),
+                3 => curve.curve_minmax_curve_frame(frame, random.next(), random.next(), random.next()),
+                _ => curve.curve_cubic_splice_frame(frame, random.value(), random.value(), random.value()),
+            }
+        }
+        curve
+    }
+
+    #[test]
+    fn test_binary() {
+        let mut random = Random(7);
+        let amountcalc = AnimationAmountCalc::default();
+        for round in 0..300 {
+            let curve = random_curve(&mut random, round % 6);
+            let bytes = curve.to_binary();
+            assert_eq!(bytes.len() % 8, 0);
+            let result: FrameCurve<[f32; 2]> = FrameCurve::from_binary(&bytes).unwrap();
+            assert_eq!(format!("{:?}", result), format!("{:?}", curve));
+            assert_eq!(result.to_binary(), bytes);
+            if !curve.frames.is_empty() || curve.curve_type() as u8 == 2 || curve.curve_type() as u8 == 4 {
+                for time in [0., 0.3, 1.7, 25.] {
+                    assert_eq!(result.interple(time, &amountcalc), curve.interple(time, &amountcalc));
+                }
+            }
+
+            // 截断的数据均返回错误
+            for len in 0..bytes.len() {
+                assert!(FrameCurve::<[f32; 2]>::read_binary(&bytes[..len]).is_err());
+            }
+            // 随机改写字节不会 panic, 成功读出的曲线可再次写出
+            for _ in 0..20 {
+                let mut corrupted = bytes.clone();
+                let index = (random.next() * bytes.len() as f32) as usize;
+                corrupted[index] = (random.next() * 256.) as u8;
+                if let Ok((curve, _)) = FrameCurve::<[f32; 2]>::read_binary(&corrupted) {
+                    curve.to_binary();
+                }
+            }
+        }
+
+        // 头部校验
+        let curve: FrameCurve<f32> = FrameCurve::curve_easing(0., 1., 30, 30, EEasingMode::ElasticOut);
+        let mut bytes = curve.to_binary();
+        assert!(matches!(FrameCurve::<f64, f64>::read_binary(&bytes), Err(ErrorCurve::BinaryValueTypeMismatch)));
+        assert!(matches!(FrameCurve::<[f32; 2]>::read_binary(&bytes), Err(ErrorCurve::BinaryValueTypeMismatch)));
+        bytes[4..6].copy_from_slice(&(BINARY_VERSION + 1).to_le_bytes());
+        assert!(matches!(FrameCurve::<f32>::read_binary(&bytes), Err(ErrorCurve::BinaryUnsupportedVersion)));
+        bytes[0] = b'X';
+        assert!(matches!(FrameCurve::<f32>::read_binary(&bytes), Err(ErrorCurve::BinaryInvalidHeader)));
+
+        // 多条记录连续存放
+        let mut bytes = Vec::new();
+        let amountcalcs = [AnimationAmountCalc::default(), AnimationAmountCalc::from_easing(EEasingMode::QuintInOut), AnimationAmountCalc::from_steps(4, EStepMode::JumpBoth), AnimationAmountCalc::from_cubic_bezier(0.1, 0.7, 0.2, 1.0)];
+        amountcalcs.iter().for_each(|amountcalc| amountcalc.write_binary(&mut bytes));
+        let mut offset = 0;
+        for amountcalc in amountcalcs.iter() {
+            let (result, size) = AnimationAmountCalc::<f32>::read_binary(&bytes[offset..]).unwrap();
+            offset += size;
+            assert_eq!(format!("{:?}", result.mode()), format!("{:?}", amountcalc.mode()));
+            for amount in [0., 0.2, 0.5, 0.9] {
+                assert_eq!(result.calc(amount), amountcalc.calc(amount));
+            }
+        }
+        assert_eq!(offset, bytes.len());
+        assert!(AnimationAmountCalc::<f32>::read_binary(&bytes[..10]).is_err());
+    }
+
     #[bench]
     fn test_minmaxcurve_peformance(b: &mut Bencher) {
     
@@ -144,4 +627,140 @@ mod test_frame {
             }
         });
     }   
-}
\ No newline at end of file
+
+    #[bench]
+    fn test_bank_peformance(b: &mut Bencher) {
+        let frame_count = 60;
+        let mut bank: CurveBank<f32> = CurveBank::new(60, ECurveBankMode::Linear, 1_000_000);
+        bank.add_key(0, &vec![0.0f32; 1_000_000]).unwrap();
+        bank.add_key(frame_count as FrameIndex, &vec![1.0f32; 1_000_000]).unwrap();
+
+        let mut result = vec![0.0f32; 1_000_000];
+        b.iter(|| {
+            bank.evaluate_all(10.0 / 60., &mut result).unwrap();
+        });
+    }
+
+    #[bench]
+    fn test_ease_slice_peformance(b: &mut Bencher) {
+        let amounts: Vec<f32> = (0..100_000).map(|i| i as f32 / 100_000.).collect();
+        let mut values = amounts.clone();
+        b.iter(|| {
+            values.copy_from_slice(&amounts);
+            ease_slice(EEasingMode::BackInOut, &mut values);
+        });
+    }
+
+    #[bench]
+    fn test_ease_scalar_peformance(b: &mut Bencher) {
+        let amounts: Vec<f32> = (0..100_000).map(|i| i as f32 / 100_000.).collect();
+        let mut values = amounts.clone();
+        b.iter(|| {
+            values.copy_from_slice(&amounts);
+            for value in values.iter_mut() {
+                *value = easing_call(*value, &EEasingMode::BackInOut);
+            }
+        });
+    }
+
+    #[bench]
+    fn test_hermite_slice_peformance(b: &mut Bencher) {
+        let amounts: Vec<f32> = (0..100_000).map(|i| i as f32 / 100_000.).collect();
+        let mut values = amounts.clone();
+        b.iter(|| {
+            values.copy_from_slice(&amounts);
+            hermite_slice(0.0, 2.0, 1.0, 2.0, &mut values);
+        });
+    }
+
+    #[bench]
+    fn test_hermite_scalar_peformance(b: &mut Bencher) {
+        let amounts: Vec<f32> = (0..100_000).map(|i| i as f32 / 100_000.).collect();
+        let mut values = amounts.clone();
+        b.iter(|| {
+            values.copy_from_slice(&amounts);
+            for value in values.iter_mut() {
+                *value = hermite(0.0, 2.0, 1.0, 2.0, *value);
+            }
+        });
+    }
+
+    #[cfg(feature = "rayon")]
+    #[bench]
+    fn test_parallel_peformance(b: &mut Bencher) {
+        use pi_curves::curve::parallel::par_interple;
+
+        let mut curves = vec![];
+        for _ in 0..1_000_000 {
+            let mut key_frames = FrameCurve::curve_frame_values(60);
+            FrameCurve::curve_frame_values_frame(&mut key_frames, 0, 0.0f32);
+            FrameCurve::curve_frame_values_frame(&mut key_frames, 60, 1.0f32);
+            curves.push(key_frames);
+        }
+        let mut output = vec![0.0f32; 1_000_000];
+        b.iter(|| {
+            par_interple(&curves, 10.0, &AnimationAmountCalc::default(), &mut output).unwrap();
+        });
+    }
+
+    #[bench]
+    fn test_easing_lut_peformance(b: &mut Bencher) {
+        let amountcalc = AnimationAmountCalc::from_lut(EasingLut::new(&AnimationAmountCalc::from_easing(EEasingMode::ElasticInOut), 1024, ELutInterpolation::Linear).into());
+        b.iter(|| {
+            let mut v = 0.0f32;
+            for i in 0..100_000 {
+                v += amountcalc.calc(i as f32 / 100_000.);
+            }
+            v
+        });
+    }
+
+    #[bench]
+    fn test_easing_direct_peformance(b: &mut Bencher) {
+        let amountcalc = AnimationAmountCalc::from_easing(EEasingMode::ElasticInOut);
+        b.iter(|| {
+            let mut v = 0.0f32;
+            for i in 0..100_000 {
+                v += amountcalc.calc(i as f32 / 100_000.);
+            }
+            v
+        });
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_dispatch() {
+        // 兼容的函数指针与 match 分发结果一致
+        let curve = FrameCurve::curve_easing(0.0f32, 1.0, 60, 60, EEasingMode::QuadInOut);
+        let amountcalc = AnimationAmountCalc::default();
+        assert_eq!((curve.call)(&curve, 30., &amountcalc), curve.interple(0.5, &amountcalc));
+        assert_eq!((curve.easing)(0.25), easing_call(0.25, &EEasingMode::QuadInOut));
+        // 替换后的函数仍然生效
+        let mut curve = curve;
+        curve.easing = |_| 0.25;
+        assert_eq!(curve.interple(0.5, &amountcalc), 0.25);
+        curve.call = |_, _, _| 2.0;
+        assert_eq!(curve.interple(0.5, &amountcalc), 2.0);
+        let mode = EAmountMode::Steps(EStepMode::JumpEnd);
+        let param = AmountParam(4., 0., 0., 0.);
+        assert_eq!(EAmountMode::get_transform_amount_call(mode)(0.3, &param), mode.transform_amount(0.3, &param));
+    }
+
+    #[bench]
+    fn test_sample_dispatch_peformance(b: &mut Bencher) {
+        // 单条曲线反复采样, 不受内存访问影响, 主要为分发开销
+        let mut linear = FrameCurve::curve_frame_values(60);
+        linear.curve_frame_values_frame(0, 0.0f32);
+        linear.curve_frame_values_frame(60, 1.0f32);
+        let easing = FrameCurve::curve_easing(0.0f32, 1.0, 60, 60, EEasingMode::QuadInOut);
+        let amountcalc = AnimationAmountCalc::default();
+        b.iter(|| {
+            let mut v = 0.0f32;
+            for i in 0..1_000_000 {
+                let time = (i % 60) as f32 / 60.;
+                v += linear.interple(time, &amountcalc) + easing.interple(time, &amountcalc);
+            }
+            v
+        });
+    }
+}
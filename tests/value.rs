@@ -0,0 +1,302 @@
+use pi_curves::{curve::{frame::{FrameDataValue, FrameValueSize}, frame_curve::FrameCurve}, easing::EEasingMode, amount::AnimationAmountCalc};
+
+#[test]
+fn test_integer_interpolate_round() {
+    assert_eq!(0u8.interpolate(&255u8, 0.5), 128);
+    assert_eq!(0u8.interpolate(&255u8, 0.999), 255);
+    assert_eq!(0u8.interpolate(&255u8, 1.0), 255);
+    assert_eq!(10u16.interpolate(&20u16, 0.26), 13);
+    assert_eq!((-100i8).interpolate(&100i8, 0.25), -50);
+    assert_eq!((-1000i64).interpolate(&1000i64, 0.5), 0);
+}
+
+#[test]
+fn test_integer_wide_precision() {
+    // 超过 2^53 后 f64 无法表示相邻整数, 64 位类型应精确计算
+    let base = 1u64 << 53;
+    assert_eq!(base.interpolate(&(base + 2), 0.5f32), base + 1);
+    assert_eq!((base + 1).interpolate(&(base + 3), 0.0f32), base + 1);
+    assert_eq!((base + 1).append(&1u64, 1.0f32), base + 2);
+    assert_eq!(u64::MAX.interpolate(&(u64::MAX - 10), 0.3f64), u64::MAX - 3);
+    assert_eq!(u64::MAX.append(&u64::MAX, 1.0f32), u64::MAX);
+    assert_eq!(0u64.append(&1u64, -1.0f32), 0);
+    assert_eq!((i64::MIN + 1).interpolate(&i64::MAX, 0.5f64), 0);
+    assert_eq!((-(1i64 << 53) - 1).interpolate(&(-(1i64 << 53) - 5), 0.5f32), -(1i64 << 53) - 3);
+    assert_eq!(u64::hermite(&(base + 1), &0, &(base + 1), &0, 0.5f32, 1.0), base + 1);
+    assert_eq!(i64::hermite(&(i64::MAX - 1), &0, &(i64::MAX - 1), &0, 0.3f64, 1.0), i64::MAX - 1);
+
+    let mut curve: FrameCurve<u64> = FrameCurve::curve_frame_values(60);
+    curve.curve_frame_values_frame(0, base + 1);
+    curve.curve_frame_values_frame(60, base + 61);
+    assert_eq!(curve.interple(0.5, &AnimationAmountCalc::default()), base + 31);
+}
+
+#[test]
+fn test_integer_saturate() {
+    // BackIn 起始段会低于 0, 应饱和到 0 而不是回绕
+    let curve = FrameCurve::curve_easing(0u8, 255u8, 60, 60, EEasingMode::BackIn);
+    for i in 0..=36 {
+        let v = curve.interple(i as f32 / 60., &AnimationAmountCalc::default());
+        assert_eq!(v, 0, "frame {}", i);
+    }
+    assert_eq!(curve.interple(1.0, &AnimationAmountCalc::default()), 255);
+
+    // ElasticOut 会超过终点, 应饱和到 i8::MAX
+    let curve = FrameCurve::curve_easing(0i8, 127i8, 60, 60, EEasingMode::ElasticOut);
+    let v = curve.interple(10. / 60., &AnimationAmountCalc::default());
+    assert_eq!(v, i8::MAX);
+
+    assert_eq!(u8::hermite(&250, &100, &255, &0, 0.5, 1.0), 255);
+    assert_eq!(i16::hermite(&-32760, &-1000, &-32760, &-1000, 0.3, 1.0), i16::MIN);
+}
+
+#[test]
+fn test_integer_delta() {
+    // 差量以补码回绕保存, 叠加时取使结果落在类型范围内的真实差值
+    let delta = FrameDataValue::<f32>::delta(&10u8, &20u8);
+    assert_eq!(delta, 246);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&100u8, &delta, 1.0), 90);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&100u8, &delta, 0.5), 95);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&20u8, &delta, 1.0), 10);
+
+    assert_eq!(FrameDataValue::<f32>::delta(&-100i8, &100i8), 56);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&0i8, &-10i8, 0.5), -5);
+    let delta = FrameDataValue::<f64>::delta(&(1u64 << 60), &((1u64 << 60) + 3));
+    assert_eq!(FrameDataValue::<f64>::add_delta(&u64::MAX, &delta, 1.0), u64::MAX - 3);
+
+    // 数组与元组逐元素计算
+    let delta = FrameDataValue::<f32>::delta(&[1u16, 8], &[3, 2]);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&[10u16, 10], &delta, 1.0), [8, 16]);
+    let delta = FrameDataValue::<f32>::delta(&(1.5f32, 4i32), &(0.5, 6));
+    assert_eq!(delta, (1.0, -2));
+}
+
+#[test]
+fn test_integer_delta_large() {
+    // |delta| 超过类型范围一半时仍能还原
+    let delta = FrameDataValue::<f32>::delta(&200u8, &10u8);
+    assert_eq!(delta, 190);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&10u8, &delta, 1.0), 200);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&10u8, &delta, 0.5), 105);
+    let delta = FrameDataValue::<f32>::delta(&10u8, &200u8);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&200u8, &delta, 1.0), 10);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&200u8, &delta, 0.5), 105);
+
+    let delta = FrameDataValue::<f32>::delta(&100i8, &-100i8);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&-100i8, &delta, 1.0), 100);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&-100i8, &delta, 0.5), 0);
+    let delta = FrameDataValue::<f32>::delta(&i16::MIN, &i16::MAX);
+    assert_eq!(FrameDataValue::<f32>::add_delta(&i16::MAX, &delta, 1.0), i16::MIN);
+
+    let delta = FrameDataValue::<f64>::delta(&u64::MAX, &1u64);
+    assert_eq!(FrameDataValue::<f64>::add_delta(&1u64, &delta, 1.0), u64::MAX);
+    let delta = FrameDataValue::<f64>::delta(&i64::MIN, &i64::MAX);
+    assert_eq!(FrameDataValue::<f64>::add_delta(&i64::MAX, &delta, 1.0), i64::MIN);
+    assert_eq!(FrameDataValue::<f64>::add_delta(&i64::MAX, &delta, 0.5), -1);
+
+    // 任意 a, b: b + (a - b) == a
+    for a in [0u8, 1, 127, 128, 200, 255] {
+        for b in [0u8, 1, 127, 128, 200, 255] {
+            let delta = FrameDataValue::<f32>::delta(&a, &b);
+            assert_eq!(FrameDataValue::<f32>::add_delta(&b, &delta, 1.0), a);
+            let (a, b) = (a as i8, b as i8);
+            let delta = FrameDataValue::<f32>::delta(&a, &b);
+            assert_eq!(FrameDataValue::<f32>::add_delta(&b, &delta, 1.0), a);
+        }
+    }
+}
+
+#[test]
+fn test_integer_curve_frame_values() {
+    let mut curve = FrameCurve::curve_frame_values(60);
+    curve.curve_frame_values_frame(0, 0u8);
+    curve.curve_frame_values_frame(60, 255u8);
+
+    let mut last = 0;
+    for i in 0..=60 {
+        let v = curve.interple(i as f32 / 60., &AnimationAmountCalc::default());
+        assert!(v >= last);
+        last = v;
+    }
+    assert_eq!(curve.interple(59. / 60., &AnimationAmountCalc::default()), 251);
+    assert_eq!(last, 255);
+}
+
+/// 以逐分量的 f32 曲线为参照, 检查复合类型在所有曲线类型上的结果
+fn check_all_curves<T: FrameDataValue + std::fmt::Debug>(a: T, b: T, components: fn(&T) -> Vec<f32>) {
+    let ca = components(&a);
+    let cb = components(&b);
+    let calc = AnimationAmountCalc::default();
+
+    let mut curves = vec![];
+    let mut refs: Vec<Vec<FrameCurve<f32>>> = vec![];
+
+    let mut curve = FrameCurve::curve_frame_values(60);
+    curve.curve_frame_values_frame(0, a.clone());
+    curve.curve_frame_values_frame(60, b.clone());
+    curves.push(curve);
+    refs.push(ca.iter().zip(cb.iter()).map(|(a, b)| {
+        let mut curve = FrameCurve::curve_frame_values(60);
+        curve.curve_frame_values_frame(0, *a);
+        curve.curve_frame_values_frame(60, *b);
+        curve
+    }).collect());
+
+    curves.push(FrameCurve::curve_easing(a.clone(), b.clone(), 60, 60, EEasingMode::BackInOut));
+    refs.push(ca.iter().zip(cb.iter()).map(|(a, b)| FrameCurve::curve_easing(*a, *b, 60, 60, EEasingMode::BackInOut)).collect());
+
+    curves.push(FrameCurve::curve_cubic_bezier(a.clone(), b.clone(), 60, 60, 0.42, 0., 0.58, 1.));
+    refs.push(ca.iter().zip(cb.iter()).map(|(a, b)| FrameCurve::curve_cubic_bezier(*a, *b, 60, 60, 0.42, 0., 0.58, 1.)).collect());
+
+    let mut curve = FrameCurve::curve_minmax_curve(a.clone(), b.clone(), 60);
+    curve.curve_minmax_curve_frame(0, 0., 2., 2.);
+    curve.curve_minmax_curve_frame(60, 1., 2., 2.);
+    curves.push(curve);
+    refs.push(ca.iter().zip(cb.iter()).map(|(a, b)| {
+        let mut curve = FrameCurve::curve_minmax_curve(*a, *b, 60);
+        curve.curve_minmax_curve_frame(0, 0., 2., 2.);
+        curve.curve_minmax_curve_frame(60, 1., 2., 2.);
+        curve
+    }).collect());
+
+    let mut curve = FrameCurve::curve_cubic_spline(60);
+    curve.curve_cubic_splice_frame(0, a.clone(), b.clone(), b.clone());
+    curve.curve_cubic_splice_frame(60, b.clone(), a.clone(), a.clone());
+    curves.push(curve);
+    refs.push(ca.iter().zip(cb.iter()).map(|(a, b)| {
+        let mut curve = FrameCurve::curve_cubic_spline(60);
+        curve.curve_cubic_splice_frame(0, *a, *b, *b);
+        curve.curve_cubic_splice_frame(60, *b, *a, *a);
+        curve
+    }).collect());
+
+    for (curve, refs) in curves.iter().zip(refs.iter()) {
+        for i in 0..=12 {
+            let t = i as f32 / 12.;
+            let v = components(&curve.interple(t, &calc));
+            let expect: Vec<f32> = refs.iter().map(|c| c.interple(t, &calc)).collect();
+            assert_eq!(v.len(), expect.len());
+            for (v, e) in v.iter().zip(expect.iter()) {
+                assert!((v - e).abs() < 1e-4, "{:?} at {}: {:?} != {:?}", curve, t, v, e);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_array_tuple_curves() {
+    check_all_curves([0.0f32, 1.0, -2.0], [4.0f32, -1.0, 2.0], |v| v.to_vec());
+    check_all_curves([[0.0f32, 1.0], [2.0, 3.0]], [[1.0f32, 0.0], [-2.0, 5.0]], |v| v.iter().flatten().copied().collect());
+    check_all_curves((1.0f32,), (3.0f32,), |v| vec![v.0]);
+    check_all_curves((1.0f32, 2.0f64, [3.0f32, 4.0]), (-1.0f32, 6.0f64, [0.0f32, 8.0]), |v| vec![v.0, v.1 as f32, v.2[0], v.2[1]]);
+    check_all_curves(
+        (1.0f32, 2.0f32, 3.0f32, 4.0f32, 5.0f32, 6.0f32, 7.0f32, 8.0f32),
+        (8.0f32, 7.0f32, 6.0f32, 5.0f32, 4.0f32, 3.0f32, 2.0f32, 1.0f32),
+        |v| vec![v.0, v.1, v.2, v.3, v.4, v.5, v.6, v.7],
+    );
+    // 单位为字节: 标量为 size_of, 复合类型为分量之和
+    assert_eq!((f32::size(), f64::size(), u8::size(), i64::size()), (4, 8, 1, 8));
+    assert_eq!(<[f32; 4]>::size(), 4 * f32::size());
+    assert_eq!(<(u8, [i16; 2])>::size(), u8::size() + 2 * i16::size());
+}
+
+#[cfg(feature = "nalgebra")]
+/// 只实现 FrameValueScale<f64> 的自定义类型
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Meters(f64);
+
+impl std::ops::Add for Meters {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Meters(self.0 + rhs.0)
+    }
+}
+
+impl pi_curves::curve::frame::FrameValueScale<f64> for Meters {
+    fn scale(&self, rhs: f64) -> Self {
+        Meters(self.0 * rhs)
+    }
+}
+
+#[test]
+fn test_f64_only_value() {
+    let mut curve: FrameCurve<Meters, f64> = FrameCurve::curve_frame_values(60);
+    curve.curve_frame_values_frame(0, Meters(0.));
+    curve.curve_frame_values_frame(60, Meters(10.));
+    assert_eq!(curve.interple(0.25, &AnimationAmountCalc::default()), Meters(2.5));
+    assert_eq!(FrameDataValue::<f64>::delta(&Meters(3.), &Meters(1.)), Meters(2.));
+}
+
+#[test]
+fn test_nalgebra_curves() {
+    use nalgebra::{DVector, Matrix3, Point3, SVector, Vector2, Vector4};
+
+    check_all_curves(Vector2::new(0.0f32, 1.0), Vector2::new(2.0f32, -1.0), |v| v.iter().copied().collect());
+    check_all_curves(Vector4::new(0.0f32, 1.0, 2.0, 3.0), Vector4::new(2.0f32, -1.0, 0.0, 1.0), |v| v.iter().copied().collect());
+    check_all_curves(SVector::<f32, 6>::from_fn(|i, _| i as f32), SVector::<f32, 6>::from_fn(|i, _| -(i as f32)), |v| v.iter().copied().collect());
+    check_all_curves(Matrix3::<f32>::identity(), Matrix3::<f32>::from_fn(|r, c| (r * 3 + c) as f32), |v| v.iter().copied().collect());
+    check_all_curves(Matrix3::<f64>::identity(), Matrix3::<f64>::from_fn(|r, c| (r * 3 + c) as f64), |v| v.iter().map(|x| *x as f32).collect());
+    check_all_curves(Point3::new(0.0f32, 1.0, 2.0), Point3::new(3.0f32, -1.0, 0.5), |v| v.iter().copied().collect());
+    check_all_curves(DVector::from_vec(vec![0.0f32, 1.0, 2.0]), DVector::from_vec(vec![5.0f32, 4.0, 3.0]), |v| v.iter().copied().collect());
+
+    // 四元数差量为旋转差量, 叠加时按比例缩放旋转角度
+    use nalgebra::{Quaternion, UnitQuaternion, Vector3};
+    let reference = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.5).into_inner();
+    let value = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 1.5).into_inner();
+    let delta: Quaternion<f32> = value.delta(&reference);
+    assert!((UnitQuaternion::new_normalize(delta).angle() - 1.0).abs() < 1e-5);
+    let base = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3).into_inner();
+    let result = UnitQuaternion::new_normalize(base.add_delta(&delta, 0.5));
+    let expected = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.5) * UnitQuaternion::new_normalize(base);
+    assert!(result.angle_to(&expected) < 1e-5);
+}
+
+#[cfg(feature = "glam")]
+#[test]
+fn test_glam_curves() {
+    use glam::{DVec3, Quat, Vec2, Vec3, Vec3A, Vec4};
+    use pi_curves::curve::frame::FrameValueSlerp;
+
+    check_all_curves(Vec2::new(0., 1.), Vec2::new(2., -1.), |v| v.to_array().to_vec());
+    check_all_curves(Vec3::new(0., 1., 2.), Vec3::new(2., -1., 0.5), |v| v.to_array().to_vec());
+    check_all_curves(Vec3A::new(0., 1., 2.), Vec3A::new(2., -1., 0.5), |v| v.to_array().to_vec());
+    check_all_curves(Vec4::new(0., 1., 2., 3.), Vec4::new(2., -1., 0.5, 0.), |v| v.to_array().to_vec());
+    check_all_curves(DVec3::new(0., 1., 2.), DVec3::new(2., -1., 0.5), |v| v.to_array().iter().map(|x| *x as f32).collect());
+
+    let from = Quat::IDENTITY;
+    let to = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+    let v = FrameValueSlerp::slerp(&from, &to, 0.5);
+    assert!(v.abs_diff_eq(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4), 1e-5));
+
+    // 四元数差量为旋转差量
+    let delta = FrameDataValue::<f32>::delta(&to, &Quat::from_rotation_z(0.5));
+    assert!(delta.abs_diff_eq(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2 - 0.5), 1e-5));
+    let result = FrameDataValue::<f32>::add_delta(&Quat::from_rotation_x(0.3), &delta, 0.5);
+    let expected = Quat::from_rotation_z((std::f32::consts::FRAC_PI_2 - 0.5) * 0.5) * Quat::from_rotation_x(0.3);
+    assert!(result.abs_diff_eq(expected, 1e-5));
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn test_mint_curves() {
+    use mint::{Point3, Quaternion, Vector2, Vector3, Vector4};
+    use pi_curves::curve::frame::FrameValueSlerp;
+
+    check_all_curves(Vector2 { x: 0.0f32, y: 1. }, Vector2 { x: 2., y: -1. }, |v| vec![v.x, v.y]);
+    check_all_curves(Vector3 { x: 0.0f32, y: 1., z: 2. }, Vector3 { x: 2., y: -1., z: 0.5 }, |v| vec![v.x, v.y, v.z]);
+    check_all_curves(Vector4 { x: 0.0f32, y: 1., z: 2., w: 3. }, Vector4 { x: 2., y: -1., z: 0.5, w: 0. }, |v| vec![v.x, v.y, v.z, v.w]);
+    check_all_curves(Point3 { x: 0.0f64, y: 1., z: 2. }, Point3 { x: 2., y: -1., z: 0.5 }, |v| vec![v.x as f32, v.y as f32, v.z as f32]);
+    assert_eq!((<Vector3<u8>>::size(), <Vector3<f32>>::size()), (3, 12));
+
+    // 绕 z 轴 0 度 -> 90 度, 中间为 45 度; 目标取反 (同一旋转) 结果不变
+    let half = std::f32::consts::FRAC_PI_4;
+    let from = Quaternion { v: Vector3 { x: 0.0f32, y: 0., z: 0. }, s: 1. };
+    let to = Quaternion { v: Vector3 { x: 0., y: 0., z: half.sin() }, s: half.cos() };
+    let neg = Quaternion { v: Vector3 { x: 0., y: 0., z: -half.sin() }, s: -half.cos() };
+    for to in [to, neg] {
+        let v = from.slerp(&to, 0.5);
+        let quarter = std::f32::consts::FRAC_PI_8;
+        assert!((v.v.z - quarter.sin()).abs() < 1e-5);
+        assert!((v.s - quarter.cos()).abs() < 1e-5);
+    }
+}